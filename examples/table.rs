@@ -0,0 +1,37 @@
+use simple_turing::computation::{Computation256, Computation256Symbols, DynComputation};
+use simple_turing::machine::TableMachine;
+use simple_turing::simulator::Simulator;
+use simple_turing::util;
+use simple_turing::{execute, DynVerifier};
+use simplicity::core::Context;
+use std::marker::PhantomData;
+
+fn main() {
+    let notation = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "1RB1RZ_1LB0RC_1LC1LA".to_string());
+    let machine: TableMachine = notation.parse().unwrap();
+
+    let mut simulator = Simulator::new(&machine, [0; 32], 128);
+    simulator.run_until_accepting(200).unwrap();
+    let trace = simulator.into_trace();
+    println!("{} halts after {} steps", notation, trace.len() - 1);
+
+    let computation: &dyn DynComputation = match machine.symbol_bits() {
+        1 => &PhantomData::<Computation256>,
+        2 => &PhantomData::<Computation256Symbols<2>>,
+        3 => &PhantomData::<Computation256Symbols<3>>,
+        _ => &PhantomData::<Computation256Symbols<4>>,
+    };
+
+    let mut context = Context::default();
+    let verifier = DynVerifier::new(computation, &machine).with_blank_start(128);
+    let commit = verifier.verify_computation(&mut context, trace.len());
+    println!("{}", util::encode_base64(&commit));
+
+    let computation = trace.computation256_symbols(|q| machine.state_value(q as usize));
+    let program = commit.finalize(computation).unwrap();
+    println!("{}", program.ty);
+
+    execute(&program).unwrap();
+}
@@ -0,0 +1,508 @@
+use crate::computation::Computation;
+use crate::jet;
+use crate::jet::application::Turing;
+use crate::util;
+use crate::VerificationError;
+use simplicity::core::{Context, Value};
+use simplicity::CommitNode;
+use std::rc::Rc;
+
+/// Number of bits of an index
+pub const INDEX_BITS: usize = 8;
+
+/// Computation _(of arbitrary length)_ with 256-bit tapes and 8-bit binary indices.
+///
+/// Tapes are encoded as `l = 256`-bit strings.
+/// Indices are encoded as `k = 8`-bit strings (binary encoding, most significant bit first).
+/// Unlike tapes, indices are nested to the right: `2^8 = 2 × 2^7`, see [`util::bits_value`].
+///
+/// The index `i` points to the `i`-th cell of the tape.
+/// The first cell is the most significant bit of the first byte, as in [`crate::computation::Computation256`].
+pub struct Computation256Binary {}
+
+impl Computation for Computation256Binary {
+    /// `get: 2^256 × 2^8 → 2`
+    fn get(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        get(context, INDEX_BITS)
+    }
+
+    /// `set: 2 × (2^256 × 2^8) → 2^256`
+    fn set(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        set(context, INDEX_BITS)
+    }
+
+    /// `eq_tape: 2^256 × 2^256 → 2`
+    fn eq_tape(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        CommitNode::jet(context, &jet::turing::EQ256).unwrap()
+    }
+
+    /// `eq_index: 2^8 × 2^8 → 2`
+    fn eq_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        util::eq_bits(context, INDEX_BITS)
+    }
+
+    /// Fails if the index moves off the right edge of the tape.
+    ///
+    /// `inc_index: 2^8 → 2^8`
+    fn inc_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        move_index(context, INDEX_BITS, true)
+    }
+
+    /// Fails if the index moves off the left edge of the tape.
+    ///
+    /// `dec_index: 2^8 → 2^8`
+    fn dec_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        move_index(context, INDEX_BITS, false)
+    }
+
+    /// Every 8-bit string is a valid index.
+    ///
+    /// `is_valid_index: 2^8 → 2`
+    fn is_valid_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        CommitNode::bit_true(context).unwrap()
+    }
+
+    /// `is_blank_tape: 2^256 → 2`
+    fn is_blank_tape(context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        Some(CommitNode::jet(context, &jet::turing::IS_ZERO256).unwrap())
+    }
+
+    /// `count_nonblank: 2^256 → 2^16`
+    fn count_nonblank(context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        Some(CommitNode::jet(context, &jet::turing::POPCOUNT_256).unwrap())
+    }
+
+    fn index_value(cell: usize) -> Option<Value> {
+        assert!(cell < 1 << INDEX_BITS, "Cell {} is not on the tape", cell);
+        Some(util::bits_value(cell, INDEX_BITS))
+    }
+
+    fn tape_value(tape: &[u8]) -> Option<Value> {
+        Some(Value::u256_from_slice(tape))
+    }
+}
+
+/// Get the symbol of a tape of `2^d` cells at a `d`-bit index.
+///
+/// The most significant bit of the index selects the half of the tape.
+///
+/// `get: 2^(2^d) × 2^d → 2`
+fn get(context: &mut Context<Turing>, d: usize) -> Rc<CommitNode<Turing>> {
+    assert!(d > 0, "Indices must be nonempty");
+
+    if d == 1 {
+        // 2 → 2
+        let iden_2 = CommitNode::iden(context).unwrap();
+        // 2 × 2 → 2
+        let w0 = CommitNode::take(context, iden_2.clone()).unwrap();
+        // 2 × 2 → 2
+        let w1 = CommitNode::drop(context, iden_2).unwrap();
+        // 2 × (2 × 2) → 2
+        let cond_w1_w0 = CommitNode::cond(context, w1, w0).unwrap();
+
+        // 2 → 2
+        let iden_b = CommitNode::iden(context).unwrap();
+        // (2 × 2) × 2 → 2
+        let b = CommitNode::drop(context, iden_b).unwrap();
+        // 2 × 2 → 2 × 2
+        let iden_w = CommitNode::iden(context).unwrap();
+        // (2 × 2) × 2 → 2 × 2
+        let w = CommitNode::take(context, iden_w).unwrap();
+        // (2 × 2) × 2 → 2 × (2 × 2)
+        let pair_b_w = CommitNode::pair(context, b, w).unwrap();
+
+        // (2 × 2) × 2 → 2
+        return CommitNode::comp(context, pair_b_w, cond_w1_w0).unwrap();
+    }
+
+    // Let H = 2^(2^(d - 1)) and R = 2^(d - 1)
+    // 2 → 2
+    let iden_2 = CommitNode::iden(context).unwrap();
+    // 2 × R → 2
+    let take_2_r = CommitNode::take(context, iden_2).unwrap();
+    // (H × H) × (2 × R) → 2
+    let b = CommitNode::drop(context, take_2_r).unwrap();
+
+    // R → R
+    let iden_r = CommitNode::iden(context).unwrap();
+    // (H × H) × R → R
+    let drop_w_r = CommitNode::drop(context, iden_r.clone()).unwrap();
+    // 2 × R → R
+    let drop_2_r = CommitNode::drop(context, iden_r).unwrap();
+    // (H × H) × (2 × R) → R
+    let rest = CommitNode::drop(context, drop_2_r).unwrap();
+    // H × H → H × H
+    let iden_w = CommitNode::iden(context).unwrap();
+    // (H × H) × (2 × R) → H × H
+    let w = CommitNode::take(context, iden_w).unwrap();
+    // (H × H) × (2 × R) → (H × H) × R
+    let pair_w_rest = CommitNode::pair(context, w, rest).unwrap();
+    // (H × H) × (2 × R) → 2 × ((H × H) × R)
+    let pair_b_w_rest = CommitNode::pair(context, b, pair_w_rest).unwrap();
+
+    // H → H
+    let iden_h = CommitNode::iden(context).unwrap();
+    // H × H → H
+    let take_h_h = CommitNode::take(context, iden_h.clone()).unwrap();
+    // H × H → H
+    let drop_h_h = CommitNode::drop(context, iden_h).unwrap();
+    // (H × H) × R → H
+    let w0 = CommitNode::take(context, take_h_h).unwrap();
+    // (H × H) × R → H
+    let w1 = CommitNode::take(context, drop_h_h).unwrap();
+    // (H × H) × R → H × R
+    let pair_w0_rest = CommitNode::pair(context, w0, drop_w_r.clone()).unwrap();
+    // (H × H) × R → H × R
+    let pair_w1_rest = CommitNode::pair(context, w1, drop_w_r).unwrap();
+
+    // H × R → 2
+    let get_half = get(context, d - 1);
+    // (H × H) × R → 2
+    let get_w0 = CommitNode::comp(context, pair_w0_rest, get_half.clone()).unwrap();
+    // (H × H) × R → 2
+    let get_w1 = CommitNode::comp(context, pair_w1_rest, get_half).unwrap();
+    // 2 × ((H × H) × R) → 2
+    let cond_w1_w0 = CommitNode::cond(context, get_w1, get_w0).unwrap();
+
+    // (H × H) × (2 × R) → 2
+    CommitNode::comp(context, pair_b_w_rest, cond_w1_w0).unwrap()
+}
+
+/// Set the symbol of a tape of `2^d` cells at a `d`-bit index.
+///
+/// The most significant bit of the index selects the half of the tape.
+///
+/// `set: 2 × (2^(2^d) × 2^d) → 2^(2^d)`
+fn set(context: &mut Context<Turing>, d: usize) -> Rc<CommitNode<Turing>> {
+    assert!(d > 0, "Indices must be nonempty");
+
+    if d == 1 {
+        // Let S = 2 × ((2 × 2) × 2)
+        // 2 → 2
+        let iden_2 = CommitNode::iden(context).unwrap();
+        // (2 × 2) × 2 → 2
+        let drop_w_b = CommitNode::drop(context, iden_2.clone()).unwrap();
+        // S → 2
+        let b = CommitNode::drop(context, drop_w_b).unwrap();
+        // S → 2
+        let s = CommitNode::take(context, iden_2.clone()).unwrap();
+        // 2 × 2 → 2
+        let take_2_2 = CommitNode::take(context, iden_2.clone()).unwrap();
+        // 2 × 2 → 2
+        let drop_2_2 = CommitNode::drop(context, iden_2).unwrap();
+        // (2 × 2) × 2 → 2
+        let take_w0 = CommitNode::take(context, take_2_2).unwrap();
+        // (2 × 2) × 2 → 2
+        let take_w1 = CommitNode::take(context, drop_2_2).unwrap();
+        // S → 2
+        let w0 = CommitNode::drop(context, take_w0).unwrap();
+        // S → 2
+        let w1 = CommitNode::drop(context, take_w1).unwrap();
+
+        // S → 2 × 2
+        let pair_w0_s = CommitNode::pair(context, w0, s.clone()).unwrap();
+        // S → 2 × 2
+        let pair_s_w1 = CommitNode::pair(context, s, w1).unwrap();
+        // 2 × S → 2 × 2
+        let cond_w0s_sw1 = CommitNode::cond(context, pair_w0_s, pair_s_w1).unwrap();
+
+        // S → S
+        let iden_s = CommitNode::iden(context).unwrap();
+        // S → 2 × S
+        let pair_b_iden = CommitNode::pair(context, b, iden_s).unwrap();
+
+        // S → 2 × 2
+        return CommitNode::comp(context, pair_b_iden, cond_w0s_sw1).unwrap();
+    }
+
+    // Let H = 2^(2^(d - 1)), R = 2^(d - 1) and S = 2 × ((H × H) × (2 × R))
+    // 2 → 2
+    let iden_2 = CommitNode::iden(context).unwrap();
+    // S → 2
+    let s = CommitNode::take(context, iden_2.clone()).unwrap();
+    // 2 × R → 2
+    let take_2_r = CommitNode::take(context, iden_2).unwrap();
+    // (H × H) × (2 × R) → 2
+    let drop_w_b = CommitNode::drop(context, take_2_r).unwrap();
+    // S → 2
+    let b = CommitNode::drop(context, drop_w_b).unwrap();
+
+    // R → R
+    let iden_r = CommitNode::iden(context).unwrap();
+    // 2 × R → R
+    let drop_2_r = CommitNode::drop(context, iden_r).unwrap();
+    // (H × H) × (2 × R) → R
+    let drop_w_rest = CommitNode::drop(context, drop_2_r).unwrap();
+    // S → R
+    let rest = CommitNode::drop(context, drop_w_rest).unwrap();
+
+    // H → H
+    let iden_h = CommitNode::iden(context).unwrap();
+    // H × H → H
+    let take_h_h = CommitNode::take(context, iden_h.clone()).unwrap();
+    // H × H → H
+    let drop_h_h = CommitNode::drop(context, iden_h).unwrap();
+    // (H × H) × (2 × R) → H
+    let take_w0 = CommitNode::take(context, take_h_h).unwrap();
+    // (H × H) × (2 × R) → H
+    let take_w1 = CommitNode::take(context, drop_h_h).unwrap();
+    // S → H
+    let w0 = CommitNode::drop(context, take_w0).unwrap();
+    // S → H
+    let w1 = CommitNode::drop(context, take_w1).unwrap();
+
+    // 2 × (H × R) → H
+    let set_half = set(context, d - 1);
+    // S → H × R
+    let pair_w0_rest = CommitNode::pair(context, w0.clone(), rest.clone()).unwrap();
+    // S → 2 × (H × R)
+    let pair_s_w0_rest = CommitNode::pair(context, s.clone(), pair_w0_rest).unwrap();
+    // S → H
+    let set_w0 = CommitNode::comp(context, pair_s_w0_rest, set_half.clone()).unwrap();
+    // S → H × R
+    let pair_w1_rest = CommitNode::pair(context, w1.clone(), rest).unwrap();
+    // S → 2 × (H × R)
+    let pair_s_w1_rest = CommitNode::pair(context, s, pair_w1_rest).unwrap();
+    // S → H
+    let set_w1 = CommitNode::comp(context, pair_s_w1_rest, set_half).unwrap();
+
+    // S → H × H
+    let pair_w0_set_w1 = CommitNode::pair(context, w0, set_w1).unwrap();
+    // S → H × H
+    let pair_set_w0_w1 = CommitNode::pair(context, set_w0, w1).unwrap();
+    // 2 × S → H × H
+    let cond_set_w1_w0 = CommitNode::cond(context, pair_w0_set_w1, pair_set_w0_w1).unwrap();
+
+    // S → S
+    let iden_s = CommitNode::iden(context).unwrap();
+    // S → 2 × S
+    let pair_b_iden = CommitNode::pair(context, b, iden_s).unwrap();
+
+    // S → H × H
+    CommitNode::comp(context, pair_b_iden, cond_set_w1_w0).unwrap()
+}
+
+/// Increment or decrement an `n`-bit index
+/// and fail if it moves off the right (or left) edge of the tape, i.e., if there is a carry (or borrow).
+///
+/// `move_index: 2^n → 2^n`
+pub(super) fn move_index(
+    context: &mut Context<Turing>,
+    n: usize,
+    increment: bool,
+) -> Rc<CommitNode<Turing>> {
+    // 2^n → 2 × 2^n
+    let carry = carry(context, n, increment);
+
+    // 2 → 2
+    let iden_2 = CommitNode::iden(context).unwrap();
+    // 2 × 2^n → 2
+    let c = CommitNode::take(context, iden_2).unwrap();
+    // 2 × 2^n → 2
+    let not_c = CommitNode::not(context, c).unwrap();
+    let error = if increment {
+        VerificationError::HeadOffRightEdge
+    } else {
+        VerificationError::HeadOffLeftEdge
+    };
+    // 2 × 2^n → 1
+    let verify_no_carry = CommitNode::assert(context, not_c, error.cmr()).unwrap();
+    // 2^n → 2^n
+    let iden_n = CommitNode::iden(context).unwrap();
+    // 2 × 2^n → 2^n
+    let result = CommitNode::drop(context, iden_n).unwrap();
+    // 2 × 2^n → 1 × 2^n
+    let pair_verify_result = CommitNode::pair(context, verify_no_carry, result).unwrap();
+    // 2^n → 2^n
+    let iden_n = CommitNode::iden(context).unwrap();
+    // 1 × 2^n → 2^n
+    let drop_unit = CommitNode::drop(context, iden_n).unwrap();
+    // 2 × 2^n → 2^n
+    let checked_result = CommitNode::comp(context, pair_verify_result, drop_unit).unwrap();
+
+    // 2^n → 2^n
+    CommitNode::comp(context, carry, checked_result).unwrap()
+}
+
+/// Increment or decrement an `n`-bit string (modulo 2^n)
+/// and return the carry (or borrow) alongside the result.
+///
+/// `carry: 2^n → 2 × 2^n`
+pub(super) fn carry(
+    context: &mut Context<Turing>,
+    n: usize,
+    increment: bool,
+) -> Rc<CommitNode<Turing>> {
+    assert!(n > 0, "Bit strings must be nonempty");
+
+    if n == 1 {
+        // 2 → 2
+        let iden_2 = CommitNode::iden(context).unwrap();
+        // 2 → 2
+        let not_2 = CommitNode::not(context, iden_2.clone()).unwrap();
+
+        // 2 → 2 × 2
+        return if increment {
+            CommitNode::pair(context, iden_2, not_2).unwrap()
+        } else {
+            CommitNode::pair(context, not_2.clone(), not_2).unwrap()
+        };
+    }
+
+    // Let R = 2^(n - 1)
+    // 2 → 2
+    let iden_2 = CommitNode::iden(context).unwrap();
+    // 2 × R → 2
+    let msb = CommitNode::take(context, iden_2.clone()).unwrap();
+    // R → R
+    let iden_r = CommitNode::iden(context).unwrap();
+    // 2 × R → R
+    let rest = CommitNode::drop(context, iden_r.clone()).unwrap();
+    // R → 2 × R
+    let carry_rest = carry(context, n - 1, increment);
+    // 2 × R → 2 × R
+    let carry_result_rest = CommitNode::comp(context, rest.clone(), carry_rest).unwrap();
+    // 2 × R → 2 × (2 × R)
+    let pair_msb_carry_result = CommitNode::pair(context, msb, carry_result_rest).unwrap();
+
+    // Input of the branches is the carry and result of the rest
+    // 2 × R → 2
+    let c = CommitNode::take(context, iden_2).unwrap();
+    // 2 × R → 2
+    let not_c = CommitNode::not(context, c.clone()).unwrap();
+    // 2 × R → 2
+    let bit_false = CommitNode::bit_false(context).unwrap();
+    // 2 × R → 2 × R
+    let pair_c_rest = CommitNode::pair(context, c.clone(), rest.clone()).unwrap();
+    // 2 × R → 2 × R
+    let pair_not_c_rest = CommitNode::pair(context, not_c, rest).unwrap();
+
+    // 2 × R → 2 × (2 × R)
+    let (msb_one, msb_zero) = if increment {
+        (
+            CommitNode::pair(context, c, pair_not_c_rest).unwrap(),
+            CommitNode::pair(context, bit_false, pair_c_rest).unwrap(),
+        )
+    } else {
+        (
+            CommitNode::pair(context, bit_false, pair_not_c_rest).unwrap(),
+            CommitNode::pair(context, c, pair_c_rest).unwrap(),
+        )
+    };
+    // 2 × (2 × R) → 2 × (2 × R)
+    let cond_msb = CommitNode::cond(context, msb_one, msb_zero).unwrap();
+
+    // 2 × R → 2 × (2 × R)
+    CommitNode::comp(context, pair_msb_carry_result, cond_msb).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulator::one_hot;
+    use crate::util::bits_value;
+    use simplicity::exec::BitMachine;
+
+    const CELLS: [usize; 6] = [0, 1, 7, 128, 200, 255];
+
+    #[test]
+    fn get() {
+        let mut context = Context::default();
+        // Tape cells are of type 2 only once the output is used as a bit
+        let get = Computation256Binary::get(&mut context);
+        let bit_true = CommitNode::bit_true(&mut context).unwrap();
+        let pair_get_true = CommitNode::pair(&mut context, get, bit_true).unwrap();
+        let eq_2 = util::eq_2(&mut context);
+        let program = CommitNode::comp(&mut context, pair_get_true, eq_2)
+            .unwrap()
+            .finalize(std::iter::empty())
+            .unwrap();
+        println!("{}", program.ty);
+
+        for written in CELLS {
+            let w = Value::u256_from_slice(&one_hot::<32>(written));
+
+            for cell in CELLS {
+                let i = bits_value(cell, INDEX_BITS);
+                let expected = Value::u1((written == cell) as u8);
+
+                let mut mac = BitMachine::for_program(&program);
+                mac.input(&Value::prod(w.clone(), i));
+                let output = mac.exec(&program, &()).unwrap();
+                assert_eq!(expected, output);
+            }
+        }
+    }
+
+    #[test]
+    fn set() {
+        let mut context = Context::default();
+        // Compare the output to the expected tape, which is provided as second input
+        let set = Computation256Binary::set(&mut context);
+        let iden = CommitNode::iden(&mut context).unwrap();
+        let take_set = CommitNode::take(&mut context, set).unwrap();
+        let expected = CommitNode::drop(&mut context, iden).unwrap();
+        let pair_set_expected = CommitNode::pair(&mut context, take_set, expected).unwrap();
+        let eq_tape = Computation256Binary::eq_tape(&mut context);
+        let program = CommitNode::comp(&mut context, pair_set_expected, eq_tape)
+            .unwrap()
+            .finalize(std::iter::empty())
+            .unwrap();
+        println!("{}", program.ty);
+
+        for cell in CELLS {
+            let symbol_input_output = vec![
+                (0, [0; 32], [0; 32]),
+                (0, one_hot(cell), [0; 32]),
+                (1, [0; 32], one_hot(cell)),
+                (1, one_hot(cell), one_hot(cell)),
+                (1, [0xff; 32], [0xff; 32]),
+            ];
+
+            for (symbol, input_tape, output_tape) in symbol_input_output {
+                let b = Value::u1(symbol);
+                let w = Value::u256_from_slice(&input_tape);
+                let i = bits_value(cell, INDEX_BITS);
+                let expected = Value::u256_from_slice(&output_tape);
+
+                let mut mac = BitMachine::for_program(&program);
+                mac.input(&Value::prod(Value::prod(b, Value::prod(w, i)), expected));
+                let output = mac.exec(&program, &()).unwrap();
+                assert_eq!(Value::u1(1), output);
+            }
+        }
+    }
+
+    #[test]
+    fn inc_dec_index() {
+        let mut context = Context::default();
+        let inc = Computation256Binary::inc_index(&mut context)
+            .finalize(std::iter::empty())
+            .unwrap();
+        let dec = Computation256Binary::dec_index(&mut context)
+            .finalize(std::iter::empty())
+            .unwrap();
+
+        for cell in 0..256 {
+            let input = bits_value(cell, INDEX_BITS);
+
+            let mut mac = BitMachine::for_program(&inc);
+            mac.input(&input);
+            let output = mac.exec(&inc, &());
+            if cell < 255 {
+                assert_eq!(bits_value(cell + 1, INDEX_BITS), output.unwrap());
+            } else {
+                assert!(output.is_err());
+            }
+
+            let mut mac = BitMachine::for_program(&dec);
+            mac.input(&input);
+            let output = mac.exec(&dec, &());
+            if cell > 0 {
+                assert_eq!(bits_value(cell - 1, INDEX_BITS), output.unwrap());
+            } else {
+                assert!(output.is_err());
+            }
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use crate::computation::Computation;
 use crate::jet;
 use crate::jet::application::Turing;
-use simplicity::core::Context;
+use crate::util;
+use simplicity::core::{Context, Value};
 use simplicity::CommitNode;
 use std::rc::Rc;
 
@@ -59,6 +60,29 @@ impl Computation for Computation256 {
     fn dec_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
         CommitNode::jet(context, &jet::turing::LEFT_SHIFT_256).unwrap()
     }
+
+    /// `is_valid_index: 2^256 → 2`
+    fn is_valid_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        CommitNode::jet(context, &jet::turing::IS_ONE_HOT_256).unwrap()
+    }
+
+    /// `is_blank_tape: 2^256 → 2`
+    fn is_blank_tape(context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        Some(CommitNode::jet(context, &jet::turing::IS_ZERO256).unwrap())
+    }
+
+    /// `count_nonblank: 2^256 → 2^16`
+    fn count_nonblank(context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        Some(CommitNode::jet(context, &jet::turing::POPCOUNT_256).unwrap())
+    }
+
+    fn index_value(cell: usize) -> Option<Value> {
+        Some(Value::u256_from_slice(&util::one_hot::<32>(cell)))
+    }
+
+    fn tape_value(tape: &[u8]) -> Option<Value> {
+        Some(Value::u256_from_slice(tape))
+    }
 }
 
 #[cfg(test)]
@@ -112,11 +136,11 @@ mod tests {
         let index = 0b10000000;
 
         for (symbol, input_byte, output_byte) in symbol_input_output {
-            let mut input_tape = base.clone();
+            let mut input_tape = base;
             input_tape[0] = input_byte;
-            let mut unary_index = base.clone();
+            let mut unary_index = base;
             unary_index[0] = index;
-            let mut output_tape = base.clone();
+            let mut output_tape = base;
             output_tape[0] = output_byte;
 
             let b = Value::u1(symbol);
@@ -0,0 +1,584 @@
+use crate::computation::Computation;
+use crate::jet;
+use crate::jet::application::{sha256_pair, Turing};
+use crate::util;
+use crate::verifier::VerificationError;
+use simplicity::core::{Context, Value};
+use simplicity::CommitNode;
+use std::rc::Rc;
+
+/// Computation _(of arbitrary length)_ with Merkleized tapes of `2^DEPTH` cells and `DEPTH`-bit binary indices.
+///
+/// A tape is committed to by the root of a SHA-256 Merkle tree whose leaves are the cells.
+/// A leaf is the 256-bit word of the value of its cell (zero or one).
+/// Each node is the hash of the concatenation of its children, see [`jet::turing::SHA256_PAIR`].
+///
+/// Tapes are encoded as `l = 256 + 1 + 256 · DEPTH`-bit strings:
+/// the root, the symbol of the current cell and its authentication path.
+/// The path is nested to the right and starts at the sibling just below the root: `P = 2^256 × (… × 2^256)`.
+/// Indices are encoded as `k = DEPTH`-bit strings, as in [`crate::computation::Computation256Binary`].
+///
+/// Only the root is compared by [`Computation::eq_tape`], so each configuration can open its own cell.
+/// [`Computation::get`] checks the authentication path against the root and fails otherwise.
+/// [`Computation::set`] computes the new root from the path, which was checked by `get` in the same step.
+/// See [`MerkleTape`] for the corresponding witness data.
+pub struct ComputationMerkle<const DEPTH: usize> {}
+
+impl<const DEPTH: usize> ComputationMerkle<DEPTH> {
+    /// Return the depth of the Merkle tree.
+    fn depth() -> usize {
+        assert!(DEPTH > 0, "Indices must be nonempty");
+        DEPTH
+    }
+}
+
+impl<const DEPTH: usize> Computation for ComputationMerkle<DEPTH> {
+    /// `get: (2^256 × (2 × P)) × 2^k → 2`
+    fn get(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        // Let L = 2^256 × (2 × P) and K = 2^k
+        // 2^256 → 2^256
+        let iden_256 = CommitNode::iden(context).unwrap();
+        // L → 2^256
+        let take_256_2p = CommitNode::take(context, iden_256).unwrap();
+        // L × K → 2^256
+        let r = CommitNode::take(context, take_256_2p).unwrap();
+        // 2 × P → 2 × P
+        let iden_2p = CommitNode::iden(context).unwrap();
+        // L → 2 × P
+        let drop_256_2p = CommitNode::drop(context, iden_2p).unwrap();
+        // L × K → 2 × P
+        let pair_b_p = CommitNode::take(context, drop_256_2p).unwrap();
+        // 2 → 2
+        let iden_2 = CommitNode::iden(context).unwrap();
+        // 2 × P → 2
+        let take_2_p = CommitNode::take(context, iden_2).unwrap();
+        // L × K → 2
+        let b = CommitNode::comp(context, pair_b_p.clone(), take_2_p).unwrap();
+        // P → P
+        let iden_p = CommitNode::iden(context).unwrap();
+        // 2 × P → P
+        let drop_2_p = CommitNode::drop(context, iden_p).unwrap();
+        // L × K → P
+        let p = CommitNode::comp(context, pair_b_p, drop_2_p).unwrap();
+        // K → K
+        let iden_k = CommitNode::iden(context).unwrap();
+        // L × K → K
+        let i = CommitNode::drop(context, iden_k).unwrap();
+
+        // L × K → 2^256
+        let computed_r = opened_root(context, Self::depth(), b.clone(), i, p);
+        // L × K → 2^256 × 2^256
+        let pair_computed_r_r = CommitNode::pair(context, computed_r, r).unwrap();
+        // 2^256 × 2^256 → 2
+        let eq256 = CommitNode::jet(context, &jet::turing::EQ256).unwrap();
+        // L × K → 2
+        let check_path = CommitNode::comp(context, pair_computed_r_r, eq256).unwrap();
+        // L × K → 1
+        let verify_path = CommitNode::assert(
+            context,
+            check_path,
+            VerificationError::InvalidMerklePath.cmr(),
+        )
+        .unwrap();
+
+        // L × K → 1 × 2
+        let pair_verify_b = CommitNode::pair(context, verify_path, b).unwrap();
+        // 2 → 2
+        let iden_2 = CommitNode::iden(context).unwrap();
+        // 1 × 2 → 2
+        let drop_1_2 = CommitNode::drop(context, iden_2).unwrap();
+
+        // L × K → 2
+        CommitNode::comp(context, pair_verify_b, drop_1_2).unwrap()
+    }
+
+    /// `set: 2 × ((2^256 × (2 × P)) × 2^k) → 2^256 × (2 × P)`
+    fn set(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        // Let L = 2^256 × (2 × P), K = 2^k and S = 2 × (L × K)
+        // 2 → 2
+        let iden_2 = CommitNode::iden(context).unwrap();
+        // S → 2
+        let b = CommitNode::take(context, iden_2).unwrap();
+        // P → P
+        let iden_p = CommitNode::iden(context).unwrap();
+        // 2 × P → P
+        let drop_2_p = CommitNode::drop(context, iden_p).unwrap();
+        // L → P
+        let drop_256_2p = CommitNode::drop(context, drop_2_p).unwrap();
+        // L × K → P
+        let take_l_k = CommitNode::take(context, drop_256_2p).unwrap();
+        // S → P
+        let p = CommitNode::drop(context, take_l_k).unwrap();
+        // K → K
+        let iden_k = CommitNode::iden(context).unwrap();
+        // L × K → K
+        let drop_l_k = CommitNode::drop(context, iden_k).unwrap();
+        // S → K
+        let i = CommitNode::drop(context, drop_l_k).unwrap();
+
+        // S → 2^256
+        let r = opened_root(context, Self::depth(), b.clone(), i, p.clone());
+        // S → 2 × P
+        let pair_b_p = CommitNode::pair(context, b, p).unwrap();
+
+        // S → 2^256 × (2 × P)
+        CommitNode::pair(context, r, pair_b_p).unwrap()
+    }
+
+    /// `eq_tape: (2^256 × (2 × P)) × (2^256 × (2 × P)) → 2`
+    fn eq_tape(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        let iden_256 = CommitNode::iden(context).unwrap();
+        let take_256_2p = CommitNode::take(context, iden_256).unwrap();
+        let r0 = CommitNode::take(context, take_256_2p.clone()).unwrap();
+        let r1 = CommitNode::drop(context, take_256_2p).unwrap();
+        let pair_r0_r1 = CommitNode::pair(context, r0, r1).unwrap();
+        let eq256 = CommitNode::jet(context, &jet::turing::EQ256).unwrap();
+
+        CommitNode::comp(context, pair_r0_r1, eq256).unwrap()
+    }
+
+    /// `eq_index: 2^k × 2^k → 2`
+    fn eq_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        util::eq_bits(context, Self::depth())
+    }
+
+    /// Fails if the index moves off the right edge of the tape.
+    ///
+    /// `inc_index: 2^k → 2^k`
+    fn inc_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        super::binary::move_index(context, Self::depth(), true)
+    }
+
+    /// Fails if the index moves off the left edge of the tape.
+    ///
+    /// `dec_index: 2^k → 2^k`
+    fn dec_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        super::binary::move_index(context, Self::depth(), false)
+    }
+
+    /// Every `k`-bit string is a valid index.
+    ///
+    /// `is_valid_index: 2^k → 2`
+    fn is_valid_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        CommitNode::bit_true(context).unwrap()
+    }
+
+    /// `is_blank_tape: 2^256 × (2 × P) → 2`
+    fn is_blank_tape(context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        let iden_256 = CommitNode::iden(context).unwrap();
+        let r = CommitNode::take(context, iden_256).unwrap();
+        let blank_root = Value::u256_from_slice(&MerkleTape::blank_root(Self::depth()));
+        let scribe_blank_root = CommitNode::scribe(context, &blank_root).unwrap();
+        let pair_r_blank_root = CommitNode::pair(context, r, scribe_blank_root).unwrap();
+        let eq256 = CommitNode::jet(context, &jet::turing::EQ256).unwrap();
+
+        Some(CommitNode::comp(context, pair_r_blank_root, eq256).unwrap())
+    }
+
+    fn index_value(cell: usize) -> Option<Value> {
+        assert!(
+            cell < 1 << Self::depth(),
+            "Cell {} is not on the tape",
+            cell
+        );
+        Some(util::bits_value(cell, Self::depth()))
+    }
+
+    /// The tape is opened at the first cell.
+    fn tape_value(tape: &[u8]) -> Option<Value> {
+        let tree = MerkleTape::new(tape);
+        assert_eq!(Self::depth(), tree.depth(), "Tape must have 2^DEPTH cells");
+        Some(tree.value(0))
+    }
+}
+
+/// Return the leaf of the given symbol.
+///
+/// `leaf: 2 → 2^256`
+fn leaf(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+    let leaf_1 = Value::u256_from_slice(&leaf_word(true));
+    let scribe_leaf_1 = CommitNode::scribe(context, &leaf_1).unwrap();
+    let leaf_0 = Value::u256_from_slice(&leaf_word(false));
+    let scribe_leaf_0 = CommitNode::scribe(context, &leaf_0).unwrap();
+    let cond_leaf_1_leaf_0 = CommitNode::cond(context, scribe_leaf_1, scribe_leaf_0).unwrap();
+
+    let iden_2 = CommitNode::iden(context).unwrap();
+    let unit = CommitNode::unit(context).unwrap();
+    let pair_b_unit = CommitNode::pair(context, iden_2, unit).unwrap();
+
+    CommitNode::comp(context, pair_b_unit, cond_leaf_1_leaf_0).unwrap()
+}
+
+/// Return the root of a Merkle tree of depth `d` that is opened at a cell,
+/// given programs for the symbol `b`, the `d`-bit index `i` and the authentication path `p`.
+///
+/// `opened_root: A → 2^256` where `b: A → 2`, `i: A → 2^d` and `p: A → P`
+fn opened_root(
+    context: &mut Context<Turing>,
+    d: usize,
+    b: Rc<CommitNode<Turing>>,
+    i: Rc<CommitNode<Turing>>,
+    p: Rc<CommitNode<Turing>>,
+) -> Rc<CommitNode<Turing>> {
+    let leaf = leaf(context);
+    let h = CommitNode::comp(context, b, leaf).unwrap();
+    let pair_i_p = CommitNode::pair(context, i, p).unwrap();
+    let pair_h_i_p = CommitNode::pair(context, h, pair_i_p).unwrap();
+    let root = root(context, d);
+
+    CommitNode::comp(context, pair_h_i_p, root).unwrap()
+}
+
+/// Return the root of a Merkle tree of depth `d`, given a leaf, its `d`-bit index and its authentication path.
+///
+/// The most significant bit of the index selects the half of the tree, whose root is paired with the first sibling.
+///
+/// `root: 2^256 × (2^d × P) → 2^256`
+fn root(context: &mut Context<Turing>, d: usize) -> Rc<CommitNode<Turing>> {
+    assert!(d > 0, "Indices must be nonempty");
+
+    // Let I = 2^d × P
+    // 2^256 → 2^256
+    let iden_256 = CommitNode::iden(context).unwrap();
+    // 2^256 × I → 2^256
+    let h = CommitNode::take(context, iden_256).unwrap();
+
+    let (bit, sub, sibling) = if d == 1 {
+        // 2 → 2
+        let iden_2 = CommitNode::iden(context).unwrap();
+        // 2 × 2^256 → 2
+        let take_2_256 = CommitNode::take(context, iden_2).unwrap();
+        // 2^256 × I → 2
+        let bit = CommitNode::drop(context, take_2_256).unwrap();
+        // 2^256 → 2^256
+        let iden_256 = CommitNode::iden(context).unwrap();
+        // 2 × 2^256 → 2^256
+        let drop_2_256 = CommitNode::drop(context, iden_256).unwrap();
+        // 2^256 × I → 2^256
+        let sibling = CommitNode::drop(context, drop_2_256).unwrap();
+
+        (bit, h, sibling)
+    } else {
+        // Let R = 2^(d - 1) and Q = P without its first sibling, so I = (2 × R) × (2^256 × Q)
+        // 2 → 2
+        let iden_2 = CommitNode::iden(context).unwrap();
+        // 2 × R → 2
+        let take_2_r = CommitNode::take(context, iden_2).unwrap();
+        // I → 2
+        let take_i = CommitNode::take(context, take_2_r).unwrap();
+        // 2^256 × I → 2
+        let bit = CommitNode::drop(context, take_i).unwrap();
+
+        // R → R
+        let iden_r = CommitNode::iden(context).unwrap();
+        // 2 × R → R
+        let drop_2_r = CommitNode::drop(context, iden_r).unwrap();
+        // I → R
+        let take_rest_i = CommitNode::take(context, drop_2_r).unwrap();
+        // 2^256 × I → R
+        let rest_i = CommitNode::drop(context, take_rest_i).unwrap();
+        // 2^256 → 2^256
+        let iden_256 = CommitNode::iden(context).unwrap();
+        // 2^256 × Q → 2^256
+        let take_256_q = CommitNode::take(context, iden_256).unwrap();
+        // I → 2^256
+        let drop_sibling = CommitNode::drop(context, take_256_q).unwrap();
+        // 2^256 × I → 2^256
+        let sibling = CommitNode::drop(context, drop_sibling).unwrap();
+        // Q → Q
+        let iden_q = CommitNode::iden(context).unwrap();
+        // 2^256 × Q → Q
+        let drop_256_q = CommitNode::drop(context, iden_q).unwrap();
+        // I → Q
+        let drop_rest_p = CommitNode::drop(context, drop_256_q).unwrap();
+        // 2^256 × I → Q
+        let rest_p = CommitNode::drop(context, drop_rest_p).unwrap();
+
+        // 2^256 × I → R × Q
+        let pair_rest_i_rest_p = CommitNode::pair(context, rest_i, rest_p).unwrap();
+        // 2^256 × I → 2^256 × (R × Q)
+        let pair_h_rest = CommitNode::pair(context, h, pair_rest_i_rest_p).unwrap();
+        // 2^256 × (R × Q) → 2^256
+        let root_half = root(context, d - 1);
+        // 2^256 × I → 2^256
+        let sub = CommitNode::comp(context, pair_h_rest, root_half).unwrap();
+
+        (bit, sub, sibling)
+    };
+
+    // 2^256 × 2^256 → 2^256
+    let hash_left = CommitNode::jet(context, &jet::turing::SHA256_PAIR).unwrap();
+    // 2^256 × 2^256 → 2^256
+    let iden_256 = CommitNode::iden(context).unwrap();
+    let take_sub = CommitNode::take(context, iden_256.clone()).unwrap();
+    let drop_sibling = CommitNode::drop(context, iden_256).unwrap();
+    let pair_sibling_sub = CommitNode::pair(context, drop_sibling, take_sub).unwrap();
+    let sha256_pair = CommitNode::jet(context, &jet::turing::SHA256_PAIR).unwrap();
+    let hash_right = CommitNode::comp(context, pair_sibling_sub, sha256_pair).unwrap();
+    // 2 × (2^256 × 2^256) → 2^256
+    let cond_right_left = CommitNode::cond(context, hash_right, hash_left).unwrap();
+
+    // 2^256 × I → 2^256 × 2^256
+    let pair_sub_sibling = CommitNode::pair(context, sub, sibling).unwrap();
+    // 2^256 × I → 2 × (2^256 × 2^256)
+    let pair_bit_sub_sibling = CommitNode::pair(context, bit, pair_sub_sibling).unwrap();
+
+    // 2^256 × I → 2^256
+    CommitNode::comp(context, pair_bit_sub_sibling, cond_right_left).unwrap()
+}
+
+/// Return the 256-bit word of the leaf of the given symbol.
+fn leaf_word(symbol: bool) -> [u8; 32] {
+    let mut word = [0; 32];
+    word[31] = symbol as u8;
+    word
+}
+
+/// Native Merkle tree of a tape, as in [`ComputationMerkle`].
+///
+/// Cells are updated in logarithmic time, so the tree can follow a computation step by step.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MerkleTape {
+    /// Nodes by level, from the leaves up to the root
+    levels: Vec<Vec<[u8; 32]>>,
+}
+
+impl MerkleTape {
+    /// Create the Merkle tree of the given tape.
+    ///
+    /// There are eight cells per byte, starting with the most significant bit of the first byte.
+    /// The number of cells must be a power of two (at least two).
+    pub fn new(tape: &[u8]) -> Self {
+        let cells = tape.len() * 8;
+        assert!(
+            cells.is_power_of_two(),
+            "Number of cells must be a power of two"
+        );
+
+        let leaves = (0..cells)
+            .map(|cell| leaf_word(tape[cell / 8] & (0b10000000 >> (cell % 8)) != 0))
+            .collect();
+        let mut levels: Vec<Vec<[u8; 32]>> = vec![leaves];
+
+        while levels.last().unwrap().len() > 1 {
+            let level = levels
+                .last()
+                .unwrap()
+                .chunks(2)
+                .map(|pair| sha256_pair(&pair[0], &pair[1]))
+                .collect();
+            levels.push(level);
+        }
+
+        MerkleTape { levels }
+    }
+
+    /// Return the root of the Merkle tree of the blank tape of `2^depth` cells.
+    pub fn blank_root(depth: usize) -> [u8; 32] {
+        (0..depth).fold(leaf_word(false), |node, _| sha256_pair(&node, &node))
+    }
+
+    /// Return the depth of the tree: there are `2^depth` cells.
+    pub fn depth(&self) -> usize {
+        self.levels.len() - 1
+    }
+
+    /// Return the root of the tree.
+    pub fn root(&self) -> [u8; 32] {
+        self.levels[self.depth()][0]
+    }
+
+    /// Return the symbol of the given cell.
+    pub fn get(&self, cell: usize) -> bool {
+        self.levels[0][cell] == leaf_word(true)
+    }
+
+    /// Set the symbol of the given cell and update the path to the root.
+    pub fn set(&mut self, cell: usize, symbol: bool) {
+        self.levels[0][cell] = leaf_word(symbol);
+
+        for level in 1..self.levels.len() {
+            let node = cell >> level;
+            let left = self.levels[level - 1][2 * node];
+            let right = self.levels[level - 1][2 * node + 1];
+            self.levels[level][node] = sha256_pair(&left, &right);
+        }
+    }
+
+    /// Return the authentication path of the given cell, starting with the sibling just below the root.
+    pub fn path(&self, cell: usize) -> Vec<[u8; 32]> {
+        (0..self.depth())
+            .rev()
+            .map(|level| self.levels[level][(cell >> level) ^ 1])
+            .collect()
+    }
+
+    /// Return the tape value that is opened at the given cell, as witness for [`ComputationMerkle`].
+    pub fn value(&self, cell: usize) -> Value {
+        let mut path = self.path(cell).into_iter().rev();
+        let bottom = Value::u256_from_slice(&path.next().unwrap());
+        let path = path.fold(bottom, |rest, sibling| {
+            Value::prod(Value::u256_from_slice(&sibling), rest)
+        });
+
+        Value::prod(
+            Value::u256_from_slice(&self.root()),
+            Value::prod(Value::u1(self.get(cell) as u8), path),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::bits_value;
+    use simplicity::exec::{BitMachine, ExecutionError};
+
+    const DEPTH: usize = 5;
+    const CELLS: [usize; 5] = [0, 1, 7, 16, 31];
+
+    #[test]
+    fn merkle_tape() {
+        let mut tape = [0b10100000, 0, 0, 0b00000001];
+        let mut tree = MerkleTape::new(&tape);
+        assert_eq!(DEPTH, tree.depth());
+        assert!(tree.get(0) && !tree.get(1) && tree.get(2) && tree.get(31));
+
+        tape[2] = 0b00100000;
+        tree.set(18, true);
+        assert_eq!(MerkleTape::new(&tape), tree);
+        assert_eq!(
+            MerkleTape::new(&[0; 4]).root(),
+            MerkleTape::blank_root(DEPTH)
+        );
+    }
+
+    #[test]
+    fn get() {
+        let mut context = Context::default();
+        // Tape cells are of type 2 only once the output is used as a bit
+        let get = ComputationMerkle::<DEPTH>::get(&mut context);
+        let bit_true = CommitNode::bit_true(&mut context).unwrap();
+        let pair_get_true = CommitNode::pair(&mut context, get, bit_true).unwrap();
+        let eq_2 = util::eq_2(&mut context);
+        let program = CommitNode::comp(&mut context, pair_get_true, eq_2)
+            .unwrap()
+            .finalize(std::iter::empty())
+            .unwrap();
+        println!("{}", program.ty);
+
+        let tree = MerkleTape::new(&[0b10000001, 0, 0x80, 1]);
+        let other = MerkleTape::new(&[0; 4]);
+
+        for cell in CELLS {
+            let i = bits_value(cell, DEPTH);
+            let expected = Value::u1(tree.get(cell) as u8);
+
+            let mut mac = BitMachine::for_program(&program);
+            mac.input(&Value::prod(tree.value(cell), i.clone()));
+            let output = mac.exec(&program, &()).unwrap();
+            assert_eq!(expected, output);
+
+            // Path of a different tape
+            let mut mac = BitMachine::for_program(&program);
+            mac.input(&Value::prod(
+                Value::prod(
+                    Value::u256_from_slice(&tree.root()),
+                    match other.value(cell) {
+                        Value::Prod(_, b_p) => *b_p,
+                        _ => unreachable!(),
+                    },
+                ),
+                i.clone(),
+            ));
+            let error = mac.exec(&program, &()).unwrap_err();
+            assert!(matches!(error, ExecutionError::ReachedPrunedBranch(cmr)
+                if cmr == VerificationError::InvalidMerklePath.cmr()));
+
+            // Path of a different cell
+            let mut mac = BitMachine::for_program(&program);
+            mac.input(&Value::prod(tree.value(cell ^ 1), i));
+            assert!(mac.exec(&program, &()).is_err());
+        }
+    }
+
+    #[test]
+    fn inc_dec_index() {
+        let mut context = Context::default();
+        let inc = ComputationMerkle::<DEPTH>::inc_index(&mut context)
+            .finalize(std::iter::empty())
+            .unwrap();
+        let dec = ComputationMerkle::<DEPTH>::dec_index(&mut context)
+            .finalize(std::iter::empty())
+            .unwrap();
+        let last = (1 << DEPTH) - 1;
+
+        for cell in CELLS {
+            let input = bits_value(cell, DEPTH);
+
+            let mut mac = BitMachine::for_program(&inc);
+            mac.input(&input);
+            let output = mac.exec(&inc, &());
+            if cell < last {
+                assert_eq!(bits_value(cell + 1, DEPTH), output.unwrap());
+            } else {
+                assert!(output.is_err());
+            }
+
+            let mut mac = BitMachine::for_program(&dec);
+            mac.input(&input);
+            let output = mac.exec(&dec, &());
+            if cell > 0 {
+                assert_eq!(bits_value(cell - 1, DEPTH), output.unwrap());
+            } else {
+                assert!(output.is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn set() {
+        let mut context = Context::default();
+        // Compare the output root to the expected root, which is provided as second input
+        let set = ComputationMerkle::<DEPTH>::set(&mut context);
+        let iden = CommitNode::iden(&mut context).unwrap();
+        let take_r = CommitNode::take(&mut context, iden.clone()).unwrap();
+        let set_r = CommitNode::comp(&mut context, set, take_r).unwrap();
+        let take_set_r = CommitNode::take(&mut context, set_r).unwrap();
+        let expected = CommitNode::drop(&mut context, iden).unwrap();
+        let pair_set_expected = CommitNode::pair(&mut context, take_set_r, expected).unwrap();
+        let eq256 = CommitNode::jet(&mut context, &jet::turing::EQ256).unwrap();
+        let check_set = CommitNode::comp(&mut context, pair_set_expected, eq256).unwrap();
+        // The old root and symbol are read by get, which precedes set in each step
+        let get = ComputationMerkle::<DEPTH>::get(&mut context);
+        let drop_get = CommitNode::drop(&mut context, get).unwrap();
+        let take_drop_get = CommitNode::take(&mut context, drop_get).unwrap();
+        let program = CommitNode::pair(&mut context, check_set, take_drop_get)
+            .unwrap()
+            .finalize(std::iter::empty())
+            .unwrap();
+        println!("{}", program.ty);
+
+        let tree = MerkleTape::new(&[0b10000001, 0, 0x80, 1]);
+
+        for cell in CELLS {
+            for symbol in [false, true] {
+                let mut expected = tree.clone();
+                expected.set(cell, symbol);
+                let expected = Value::u256_from_slice(&expected.root());
+
+                let input = Value::prod(
+                    Value::u1(symbol as u8),
+                    Value::prod(tree.value(cell), bits_value(cell, DEPTH)),
+                );
+                let mut mac = BitMachine::for_program(&program);
+                mac.input(&Value::prod(input, expected));
+                let output = mac.exec(&program, &()).unwrap();
+                assert_eq!(
+                    Value::prod(Value::u1(1), Value::u1(tree.get(cell) as u8)),
+                    output
+                );
+            }
+        }
+    }
+}
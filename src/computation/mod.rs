@@ -1,10 +1,19 @@
+mod binary;
 mod default;
+mod merkle;
+mod symbols;
+mod wide;
 
 use crate::jet::application::Turing;
+pub use binary::{Computation256Binary, INDEX_BITS};
 pub use default::Computation256;
-use simplicity::core::Context;
+pub use merkle::{ComputationMerkle, MerkleTape};
+use simplicity::core::{Context, Value};
 use simplicity::CommitNode;
+use std::marker::PhantomData;
 use std::rc::Rc;
+pub use symbols::Computation256Symbols;
+pub use wide::{Computation1024, Computation512, ComputationWide};
 
 /// Computation of a Turing Machine, i.e., sequence of configurations.
 /// A configuration is the current state, current tape and index which points to the current symbol.
@@ -13,15 +22,16 @@ use std::rc::Rc;
 ///
 /// Tapes are encoded as `l`-bit strings.
 /// The tape is indexed via `k`-bit strings.
+/// Tape symbols are encoded as `s`-bit strings, where `s = 1` for the binary alphabet.
 pub trait Computation {
     /// Get tape symbol at index
     ///
-    /// `get: 2^l × 2^k → 2`
+    /// `get: 2^l × 2^k → 2^s`
     fn get(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
 
     /// Set tape symbol at index to given symbol
     ///
-    /// `set: 2 × (2^l × 2^k) → 2^l`
+    /// `set: 2^s × (2^l × 2^k) → 2^l`
     fn set(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
 
     /// Check equality of two tapes
@@ -34,13 +44,189 @@ pub trait Computation {
     /// `eq_index: 2^k × 2^k → 2`
     fn eq_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
 
-    /// Increment index by one (modulo 2^k)
+    /// Increment index by one
+    ///
+    /// Fails if the index moves off the right edge of the tape, instead of wrapping around.
     ///
     /// `inc_index: 2^k → 2^k`
     fn inc_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
 
-    /// Decrement index by one (modulo 2^k)
+    /// Decrement index by one
     ///
-    /// `inc_index: 2^k → 2^k`
+    /// Fails if the index moves off the left edge of the tape, instead of wrapping around.
+    ///
+    /// `dec_index: 2^k → 2^k`
     fn dec_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
+
+    /// Check that index is well-formed, i.e., points to exactly one symbol
+    ///
+    /// The default accepts every index, which suits formats where every `k`-bit string points to a symbol.
+    ///
+    /// `is_valid_index: 2^k → 2`
+    fn is_valid_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        CommitNode::bit_true(context).unwrap()
+    }
+
+    /// Check if tape is blank, i.e., all symbols are zero
+    ///
+    /// Return `None` if the tape format cannot be checked.
+    ///
+    /// `is_blank_tape: 2^l → 2`
+    fn is_blank_tape(_context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        None
+    }
+
+    /// Count the nonblank symbols on the tape
+    ///
+    /// Return `None` if the tape format cannot be counted.
+    ///
+    /// `count_nonblank: 2^l → 2^16`
+    fn count_nonblank(_context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        None
+    }
+
+    /// Return the index that points to the given cell
+    ///
+    /// Return `None` if the index format cannot be encoded.
+    fn index_value(_cell: usize) -> Option<Value> {
+        None
+    }
+
+    /// Return the tape with the given symbols
+    ///
+    /// There are eight symbols per byte, starting with the most significant bit of the first byte.
+    /// Tapes with multi-bit symbols consist of several such bit planes, see [`Computation256Symbols`].
+    /// Return `None` if the tape format cannot be encoded.
+    fn tape_value(_tape: &[u8]) -> Option<Value> {
+        None
+    }
+}
+
+/// Computation of a Turing Machine whose format is known at runtime.
+///
+/// This is the variant of [`Computation`] that takes `&self`.
+/// Every [`Computation`] `C` is available at runtime as `PhantomData<C>`.
+pub trait DynComputation {
+    /// Get tape symbol at index
+    ///
+    /// `get: 2^l × 2^k → 2^s`
+    fn get(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
+
+    /// Set tape symbol at index to given symbol
+    ///
+    /// `set: 2^s × (2^l × 2^k) → 2^l`
+    fn set(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
+
+    /// Check equality of two tapes
+    ///
+    /// `eq_tape: 2^l × 2^l → 2`
+    fn eq_tape(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
+
+    /// Check equality of two indices
+    ///
+    /// `eq_index: 2^k × 2^k → 2`
+    fn eq_index(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
+
+    /// Increment index by one
+    ///
+    /// Fails if the index moves off the right edge of the tape, instead of wrapping around.
+    ///
+    /// `inc_index: 2^k → 2^k`
+    fn inc_index(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
+
+    /// Decrement index by one
+    ///
+    /// Fails if the index moves off the left edge of the tape, instead of wrapping around.
+    ///
+    /// `dec_index: 2^k → 2^k`
+    fn dec_index(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
+
+    /// Check that index is well-formed, i.e., points to exactly one symbol
+    ///
+    /// The default accepts every index, which suits formats where every `k`-bit string points to a symbol.
+    ///
+    /// `is_valid_index: 2^k → 2`
+    fn is_valid_index(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        CommitNode::bit_true(context).unwrap()
+    }
+
+    /// Check if tape is blank, i.e., all symbols are zero
+    ///
+    /// Return `None` if the tape format cannot be checked.
+    ///
+    /// `is_blank_tape: 2^l → 2`
+    fn is_blank_tape(&self, _context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        None
+    }
+
+    /// Count the nonblank symbols on the tape
+    ///
+    /// Return `None` if the tape format cannot be counted.
+    ///
+    /// `count_nonblank: 2^l → 2^16`
+    fn count_nonblank(&self, _context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        None
+    }
+
+    /// Return the index that points to the given cell
+    ///
+    /// Return `None` if the index format cannot be encoded.
+    fn index_value(&self, _cell: usize) -> Option<Value> {
+        None
+    }
+
+    /// Return the tape with the given symbols
+    ///
+    /// There are eight symbols per byte, starting with the most significant bit of the first byte.
+    /// Tapes with multi-bit symbols consist of several such bit planes, see [`Computation256Symbols`].
+    /// Return `None` if the tape format cannot be encoded.
+    fn tape_value(&self, _tape: &[u8]) -> Option<Value> {
+        None
+    }
+}
+
+impl<C: Computation> DynComputation for PhantomData<C> {
+    fn get(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        C::get(context)
+    }
+
+    fn set(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        C::set(context)
+    }
+
+    fn eq_tape(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        C::eq_tape(context)
+    }
+
+    fn eq_index(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        C::eq_index(context)
+    }
+
+    fn inc_index(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        C::inc_index(context)
+    }
+
+    fn dec_index(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        C::dec_index(context)
+    }
+
+    fn is_valid_index(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        C::is_valid_index(context)
+    }
+
+    fn is_blank_tape(&self, context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        C::is_blank_tape(context)
+    }
+
+    fn count_nonblank(&self, context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        C::count_nonblank(context)
+    }
+
+    fn index_value(&self, cell: usize) -> Option<Value> {
+        C::index_value(cell)
+    }
+
+    fn tape_value(&self, tape: &[u8]) -> Option<Value> {
+        C::tape_value(tape)
+    }
 }
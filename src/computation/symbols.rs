@@ -0,0 +1,382 @@
+use crate::computation::{Computation, Computation256};
+use crate::jet;
+use crate::jet::application::Turing;
+use crate::util;
+use simplicity::core::{Context, Value};
+use simplicity::CommitNode;
+use std::rc::Rc;
+
+/// Computation _(of arbitrary length)_ with 256-cell tapes of `2^SYMBOL_BITS` symbols and 256-bit one-hot indices.
+///
+/// Tape symbols are encoded as `s = SYMBOL_BITS`-bit strings, nested to the right as in [`util::bits_value`].
+/// Tapes are encoded as `s` bit planes of 256 bits each: plane `j` holds bit `j` of the symbol of each cell.
+/// The planes are nested to the right, starting with the plane of the most significant bit:
+/// `l = 256 · s`, `2^l = 2^256 × (… × 2^256)`. See [`util::planes_value`].
+/// Indices are encoded as in [`Computation256`].
+///
+/// Each plane is handled by [`Computation256`].
+/// The blank symbol is zero.
+pub struct Computation256Symbols<const SYMBOL_BITS: usize> {}
+
+impl<const SYMBOL_BITS: usize> Computation256Symbols<SYMBOL_BITS> {
+    /// Return the number of bits of a symbol.
+    fn symbol_bits() -> usize {
+        assert!(SYMBOL_BITS > 0, "Symbols must be nonempty");
+        SYMBOL_BITS
+    }
+}
+
+impl<const SYMBOL_BITS: usize> Computation for Computation256Symbols<SYMBOL_BITS> {
+    /// `get: 2^l × 2^256 → 2^s`
+    fn get(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        get(context, Self::symbol_bits())
+    }
+
+    /// `set: 2^s × (2^l × 2^256) → 2^l`
+    fn set(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        set(context, Self::symbol_bits())
+    }
+
+    /// `eq_tape: 2^l × 2^l → 2`
+    fn eq_tape(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        all_planes(context, Self::symbol_bits(), true)
+    }
+
+    /// `eq_index: 2^256 × 2^256 → 2`
+    fn eq_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        Computation256::eq_index(context)
+    }
+
+    /// `inc_index: 2^256 → 2^256`
+    fn inc_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        Computation256::inc_index(context)
+    }
+
+    /// `dec_index: 2^256 → 2^256`
+    fn dec_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        Computation256::dec_index(context)
+    }
+
+    /// `is_valid_index: 2^256 → 2`
+    fn is_valid_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        Computation256::is_valid_index(context)
+    }
+
+    /// `is_blank_tape: 2^l → 2`
+    fn is_blank_tape(context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        Some(all_planes(context, Self::symbol_bits(), false))
+    }
+
+    /// A cell is nonblank if it is set on any plane.
+    ///
+    /// `count_nonblank: 2^l → 2^16`
+    fn count_nonblank(context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        // 2^l → 2^256
+        let or_planes = or_planes(context, Self::symbol_bits());
+        // 2^256 → 2^16
+        let popcount = CommitNode::jet(context, &jet::turing::POPCOUNT_256).unwrap();
+
+        Some(CommitNode::comp(context, or_planes, popcount).unwrap())
+    }
+
+    fn index_value(cell: usize) -> Option<Value> {
+        Computation256::index_value(cell)
+    }
+
+    /// The tape consists of `s` planes of 32 bytes each, starting with the plane of the most significant bit.
+    fn tape_value(tape: &[u8]) -> Option<Value> {
+        let s = Self::symbol_bits();
+        assert_eq!(32 * s, tape.len(), "Tape must consist of {} planes", s);
+        Some(util::planes_value(
+            tape.chunks(32).map(|plane| plane.try_into().unwrap()),
+        ))
+    }
+}
+
+/// Get the symbol of a tape of `s` planes.
+///
+/// `get: 2^(256 · s) × 2^256 → 2^s`
+fn get(context: &mut Context<Turing>, s: usize) -> Rc<CommitNode<Turing>> {
+    if s == 1 {
+        return Computation256::get(context);
+    }
+
+    // Let P = 2^256 and R = 2^(256 · (s - 1))
+    // P → P
+    let iden_p = CommitNode::iden(context).unwrap();
+    // P × R → P
+    let take_p_r = CommitNode::take(context, iden_p.clone()).unwrap();
+    // (P × R) × P → P
+    let plane = CommitNode::take(context, take_p_r).unwrap();
+    // (P × R) × P → P
+    let i = CommitNode::drop(context, iden_p).unwrap();
+    // (P × R) × P → P × P
+    let pair_plane_i = CommitNode::pair(context, plane, i.clone()).unwrap();
+    // P × P → 2
+    let get_plane = Computation256::get(context);
+    // (P × R) × P → 2
+    let msb = CommitNode::comp(context, pair_plane_i, get_plane).unwrap();
+
+    // R → R
+    let iden_r = CommitNode::iden(context).unwrap();
+    // P × R → R
+    let drop_p_r = CommitNode::drop(context, iden_r).unwrap();
+    // (P × R) × P → R
+    let rest = CommitNode::take(context, drop_p_r).unwrap();
+    // (P × R) × P → R × P
+    let pair_rest_i = CommitNode::pair(context, rest, i).unwrap();
+    // R × P → 2^(s - 1)
+    let get_rest = get(context, s - 1);
+    // (P × R) × P → 2^(s - 1)
+    let rest_bits = CommitNode::comp(context, pair_rest_i, get_rest).unwrap();
+
+    // (P × R) × P → 2 × 2^(s - 1)
+    CommitNode::pair(context, msb, rest_bits).unwrap()
+}
+
+/// Set the symbol of a tape of `s` planes.
+///
+/// `set: 2^s × (2^(256 · s) × 2^256) → 2^(256 · s)`
+fn set(context: &mut Context<Turing>, s: usize) -> Rc<CommitNode<Turing>> {
+    if s == 1 {
+        return Computation256::set(context);
+    }
+
+    // Let P = 2^256, R = 2^(256 · (s - 1)) and S = (2 × 2^(s - 1)) × ((P × R) × P)
+    // 2 → 2
+    let iden_2 = CommitNode::iden(context).unwrap();
+    // 2 × 2^(s - 1) → 2
+    let take_2_bits = CommitNode::take(context, iden_2).unwrap();
+    // S → 2
+    let msb = CommitNode::take(context, take_2_bits).unwrap();
+    // 2^(s - 1) → 2^(s - 1)
+    let iden_bits = CommitNode::iden(context).unwrap();
+    // 2 × 2^(s - 1) → 2^(s - 1)
+    let drop_2_bits = CommitNode::drop(context, iden_bits).unwrap();
+    // S → 2^(s - 1)
+    let rest_bits = CommitNode::take(context, drop_2_bits).unwrap();
+
+    // P → P
+    let iden_p = CommitNode::iden(context).unwrap();
+    // P × R → P
+    let take_p_r = CommitNode::take(context, iden_p.clone()).unwrap();
+    // (P × R) × P → P
+    let take_plane = CommitNode::take(context, take_p_r).unwrap();
+    // S → P
+    let plane = CommitNode::drop(context, take_plane).unwrap();
+    // (P × R) × P → P
+    let drop_i = CommitNode::drop(context, iden_p).unwrap();
+    // S → P
+    let i = CommitNode::drop(context, drop_i).unwrap();
+    // R → R
+    let iden_r = CommitNode::iden(context).unwrap();
+    // P × R → R
+    let drop_p_r = CommitNode::drop(context, iden_r).unwrap();
+    // (P × R) × P → R
+    let take_rest = CommitNode::take(context, drop_p_r).unwrap();
+    // S → R
+    let rest = CommitNode::drop(context, take_rest).unwrap();
+
+    // S → P × P
+    let pair_plane_i = CommitNode::pair(context, plane, i.clone()).unwrap();
+    // S → 2 × (P × P)
+    let pair_msb_plane_i = CommitNode::pair(context, msb, pair_plane_i).unwrap();
+    // 2 × (P × P) → P
+    let set_plane = Computation256::set(context);
+    // S → P
+    let new_plane = CommitNode::comp(context, pair_msb_plane_i, set_plane).unwrap();
+
+    // S → R × P
+    let pair_rest_i = CommitNode::pair(context, rest, i).unwrap();
+    // S → 2^(s - 1) × (R × P)
+    let pair_rest_bits_rest_i = CommitNode::pair(context, rest_bits, pair_rest_i).unwrap();
+    // 2^(s - 1) × (R × P) → R
+    let set_rest = set(context, s - 1);
+    // S → R
+    let new_rest = CommitNode::comp(context, pair_rest_bits_rest_i, set_rest).unwrap();
+
+    // S → P × R
+    CommitNode::pair(context, new_plane, new_rest).unwrap()
+}
+
+/// Check a predicate on every plane of a tape of `s` planes:
+/// equality of two tapes if `eq` holds, or that a tape is blank otherwise.
+///
+/// `all_planes: 2^(256 · s) × 2^(256 · s) → 2` or `2^(256 · s) → 2`
+fn all_planes(context: &mut Context<Turing>, s: usize, eq: bool) -> Rc<CommitNode<Turing>> {
+    let check_plane = if eq {
+        CommitNode::jet(context, &jet::turing::EQ256).unwrap()
+    } else {
+        CommitNode::jet(context, &jet::turing::IS_ZERO256).unwrap()
+    };
+
+    if s == 1 {
+        return check_plane;
+    }
+
+    // Let P = 2^256 and R = 2^(256 · (s - 1))
+    let iden_p = CommitNode::iden(context).unwrap();
+    let take_p_r = CommitNode::take(context, iden_p).unwrap();
+    let iden_r = CommitNode::iden(context).unwrap();
+    let drop_p_r = CommitNode::drop(context, iden_r).unwrap();
+
+    let (plane, rest) = if eq {
+        // (P × R) × (P × R) → P × P
+        let first_plane = CommitNode::take(context, take_p_r.clone()).unwrap();
+        let second_plane = CommitNode::drop(context, take_p_r).unwrap();
+        let planes = CommitNode::pair(context, first_plane, second_plane).unwrap();
+        // (P × R) × (P × R) → R × R
+        let first_rest = CommitNode::take(context, drop_p_r.clone()).unwrap();
+        let second_rest = CommitNode::drop(context, drop_p_r).unwrap();
+        let rests = CommitNode::pair(context, first_rest, second_rest).unwrap();
+        (planes, rests)
+    } else {
+        (take_p_r, drop_p_r)
+    };
+
+    // → 2
+    let check_first = CommitNode::comp(context, plane, check_plane).unwrap();
+    // → 2 × (…)
+    let pair_check_rest = CommitNode::pair(context, check_first, rest).unwrap();
+    // … → 2
+    let check_rest = all_planes(context, s - 1, eq);
+    // 1 → 2
+    let bit_false = CommitNode::bit_false(context).unwrap();
+    // 2 × … → 2
+    let cond_rest_false = CommitNode::cond(context, check_rest, bit_false).unwrap();
+
+    CommitNode::comp(context, pair_check_rest, cond_rest_false).unwrap()
+}
+
+/// Combine the `s` planes of a tape by bitwise or.
+///
+/// `or_planes: 2^(256 · s) → 2^256`
+fn or_planes(context: &mut Context<Turing>, s: usize) -> Rc<CommitNode<Turing>> {
+    if s == 1 {
+        return CommitNode::iden(context).unwrap();
+    }
+
+    // Let P = 2^256 and R = 2^(256 · (s - 1))
+    // P → P
+    let iden_p = CommitNode::iden(context).unwrap();
+    // P × R → P
+    let plane = CommitNode::take(context, iden_p).unwrap();
+    // R → P
+    let or_rest = or_planes(context, s - 1);
+    // P × R → P
+    let rest = CommitNode::drop(context, or_rest).unwrap();
+    // P × R → P × P
+    let pair_plane_rest = CommitNode::pair(context, plane, rest).unwrap();
+    // P × P → P
+    let or256 = CommitNode::jet(context, &jet::turing::OR_256).unwrap();
+
+    CommitNode::comp(context, pair_plane_rest, or256).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulator::one_hot;
+    use simplicity::exec::BitMachine;
+
+    const CELLS: [usize; 4] = [0, 7, 128, 255];
+
+    /// Return the tape of two planes where each of the given cells has the given symbol.
+    fn tape(cells_symbols: &[(usize, u8)]) -> Vec<u8> {
+        let mut tape = vec![0; 64];
+        for &(cell, symbol) in cells_symbols {
+            for (plane, bit) in [(0, 2), (32, 1)] {
+                if symbol & bit != 0 {
+                    tape[plane + cell / 8] |= one_hot::<32>(cell)[cell / 8];
+                }
+            }
+        }
+        tape
+    }
+
+    #[test]
+    fn get_set() {
+        type C = Computation256Symbols<2>;
+        let mut context = Context::default();
+        let get = C::get(&mut context);
+        let scribe_3 = CommitNode::scribe(&mut context, &util::bits_value(3, 2)).unwrap();
+        let pair_get_3 = CommitNode::pair(&mut context, get, scribe_3).unwrap();
+        let eq_bits = util::eq_bits(&mut context, 2);
+        let get_is_3 = CommitNode::comp(&mut context, pair_get_3, eq_bits)
+            .unwrap()
+            .finalize(std::iter::empty())
+            .unwrap();
+
+        let set = C::set(&mut context);
+        let iden = CommitNode::iden(&mut context).unwrap();
+        let take_set = CommitNode::take(&mut context, set).unwrap();
+        let expected = CommitNode::drop(&mut context, iden).unwrap();
+        let pair_set_expected = CommitNode::pair(&mut context, take_set, expected).unwrap();
+        let eq_tape = C::eq_tape(&mut context);
+        let set_is_expected = CommitNode::comp(&mut context, pair_set_expected, eq_tape)
+            .unwrap()
+            .finalize(std::iter::empty())
+            .unwrap();
+
+        for cell in CELLS {
+            for symbol in 0..4 {
+                let w = C::tape_value(&tape(&[(cell, symbol), (3, 3)])).unwrap();
+                let i = C::index_value(cell).unwrap();
+
+                let mut mac = BitMachine::for_program(&get_is_3);
+                mac.input(&Value::prod(w.clone(), i.clone()));
+                let output = mac.exec(&get_is_3, &()).unwrap();
+                assert_eq!(Value::u1((symbol == 3) as u8), output);
+
+                let written = (symbol + 1) % 4;
+                let expected = C::tape_value(&tape(&[(cell, written), (3, 3)])).unwrap();
+                let input = Value::prod(util::bits_value(written as usize, 2), Value::prod(w, i));
+                let mut mac = BitMachine::for_program(&set_is_expected);
+                mac.input(&Value::prod(input, expected));
+                let output = mac.exec(&set_is_expected, &()).unwrap();
+                assert_eq!(Value::u1(1), output);
+            }
+        }
+    }
+
+    #[test]
+    fn is_blank_tape() {
+        type C = Computation256Symbols<3>;
+        let mut context = Context::default();
+        let program = C::is_blank_tape(&mut context)
+            .unwrap()
+            .finalize(std::iter::empty())
+            .unwrap();
+
+        for (byte, blank) in [(0, true), (31, false), (32, false), (95, false)] {
+            let mut tape = [0; 96];
+            tape[byte] = (!blank) as u8;
+
+            let mut mac = BitMachine::for_program(&program);
+            mac.input(&C::tape_value(&tape).unwrap());
+            let output = mac.exec(&program, &()).unwrap();
+            assert_eq!(Value::u1(blank as u8), output);
+        }
+    }
+
+    #[test]
+    fn count_nonblank() {
+        type C = Computation256Symbols<2>;
+        let mut context = Context::default();
+        let program = C::count_nonblank(&mut context)
+            .unwrap()
+            .finalize(std::iter::empty())
+            .unwrap();
+
+        for (cells_symbols, count) in [
+            (vec![], 0),
+            (vec![(0, 1), (7, 2), (255, 3)], 3),
+            (vec![(3, 3), (128, 1)], 2),
+        ] {
+            let mut mac = BitMachine::for_program(&program);
+            mac.input(&C::tape_value(&tape(&cells_symbols)).unwrap());
+            let output = mac.exec(&program, &()).unwrap();
+            assert_eq!(Value::u16(count), output);
+        }
+    }
+}
@@ -0,0 +1,563 @@
+use crate::computation::{Computation, Computation256};
+use crate::jet;
+use crate::jet::application::Turing;
+use crate::util;
+use simplicity::core::{Context, Value};
+use simplicity::CommitNode;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+/// Computation _(of arbitrary length)_ with 512-bit tapes and 512-bit one-hot indices.
+pub type Computation512 = ComputationWide<2>;
+
+/// Computation _(of arbitrary length)_ with 1024-bit tapes and 1024-bit one-hot indices.
+pub type Computation1024 = ComputationWide<4>;
+
+/// Computation _(of arbitrary length)_ with tapes and one-hot indices of `WORDS` 256-bit words.
+///
+/// `WORDS` must be a power of two.
+/// Tapes and indices are encoded as `l = k = 256 · WORDS`-bit strings,
+/// which are balanced trees of 256-bit words: `2^512 = 2^256 × 2^256` and so on.
+/// See [`util::words_value`].
+///
+/// Each word is handled by [`Computation256`].
+/// Moving the index across the boundary of two words carries the one-hot bit from one word to the other.
+pub struct ComputationWide<const WORDS: usize> {
+    _words: PhantomData<[(); WORDS]>,
+}
+
+impl<const WORDS: usize> ComputationWide<WORDS> {
+    /// Return the number of levels of pairing: `WORDS = 2^levels`.
+    fn levels() -> usize {
+        assert!(
+            WORDS >= 2 && WORDS.is_power_of_two(),
+            "Number of words must be a power of two (at least two)"
+        );
+        WORDS.trailing_zeros() as usize
+    }
+}
+
+impl<const WORDS: usize> Computation for ComputationWide<WORDS> {
+    /// `get: 2^l × 2^l → 2`
+    fn get(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        get(context, Self::levels())
+    }
+
+    /// `set: 2 × (2^l × 2^l) → 2^l`
+    fn set(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        set(context, Self::levels())
+    }
+
+    /// `eq_tape: 2^l × 2^l → 2`
+    fn eq_tape(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        eq(context, Self::levels())
+    }
+
+    /// `eq_index: 2^l × 2^l → 2`
+    fn eq_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        eq(context, Self::levels())
+    }
+
+    /// `inc_index: 2^l → 2^l`
+    fn inc_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        inc_index(context, Self::levels())
+    }
+
+    /// `dec_index: 2^l → 2^l`
+    fn dec_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        dec_index(context, Self::levels())
+    }
+
+    /// `is_valid_index: 2^l → 2`
+    fn is_valid_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        is_one_hot(context, Self::levels())
+    }
+
+    /// `is_blank_tape: 2^l → 2`
+    fn is_blank_tape(context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        Some(is_zero(context, Self::levels()))
+    }
+
+    /// `count_nonblank: 2^l → 2^16`
+    fn count_nonblank(context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        let popcount = match Self::levels() {
+            1 => &jet::turing::POPCOUNT_512,
+            2 => &jet::turing::POPCOUNT_1024,
+            _ => return None,
+        };
+
+        Some(CommitNode::jet(context, popcount).unwrap())
+    }
+
+    fn index_value(cell: usize) -> Option<Value> {
+        assert!(cell < 256 * WORDS, "Cell {} is not on the tape", cell);
+        let mut bytes = vec![0; 32 * WORDS];
+        bytes[cell / 8] = 0b10000000 >> (cell % 8);
+
+        Some(util::words_value(
+            bytes.chunks(32).map(|word| word.try_into().unwrap()),
+        ))
+    }
+
+    fn tape_value(tape: &[u8]) -> Option<Value> {
+        assert_eq!(
+            32 * WORDS,
+            tape.len(),
+            "Tape must consist of {} bytes",
+            32 * WORDS
+        );
+        Some(util::words_value(
+            tape.chunks(32).map(|word| word.try_into().unwrap()),
+        ))
+    }
+}
+
+/// Return the one-hot word whose first or last bit is set.
+fn edge_word(last: bool) -> [u8; 32] {
+    let mut word = [0; 32];
+
+    if last {
+        word[31] = 0b00000001;
+    } else {
+        word[0] = 0b10000000;
+    }
+
+    word
+}
+
+/// Return the value of `2^d` words, which are all zero except for the given edge word.
+fn edge_value(d: usize, last: bool) -> Value {
+    let n_words = 1 << d;
+    let zero = [0; 32];
+    let edge = edge_word(last);
+    let words = (0..n_words).map(|word| {
+        if (last && word == n_words - 1) || (!last && word == 0) {
+            &edge
+        } else {
+            &zero
+        }
+    });
+
+    util::words_value(words)
+}
+
+/// Project the first (`take`) or second (`drop`) half of both tapes in a pair of tapes.
+///
+/// `halves: (T × T) × (T × T) → T × T`
+fn halves(context: &mut Context<Turing>, second: bool) -> Rc<CommitNode<Turing>> {
+    // T → T
+    let iden = CommitNode::iden(context).unwrap();
+    // T × T → T
+    let half = if second {
+        CommitNode::drop(context, iden).unwrap()
+    } else {
+        CommitNode::take(context, iden).unwrap()
+    };
+    // (T × T) × (T × T) → T
+    let first_half = CommitNode::take(context, half.clone()).unwrap();
+    // (T × T) × (T × T) → T
+    let second_half = CommitNode::drop(context, half).unwrap();
+
+    // (T × T) × (T × T) → T × T
+    CommitNode::pair(context, first_half, second_half).unwrap()
+}
+
+/// Get the tape symbol at the one-hot index, for tapes of `2^d` words.
+///
+/// `get: T_d × T_d → 2`
+fn get(context: &mut Context<Turing>, d: usize) -> Rc<CommitNode<Turing>> {
+    if d == 0 {
+        return Computation256::get(context);
+    }
+
+    // T_(d - 1) × T_(d - 1) → 2
+    let get_half = get(context, d - 1);
+    // T_d × T_d → T_(d - 1) × T_(d - 1)
+    let first_halves = halves(context, false);
+    // T_d × T_d → T_(d - 1) × T_(d - 1)
+    let second_halves = halves(context, true);
+    // T_d × T_d → 2
+    let get_first = CommitNode::comp(context, first_halves, get_half.clone()).unwrap();
+    // T_d × T_d → 2
+    let get_second = CommitNode::comp(context, second_halves, get_half).unwrap();
+
+    // T_d × T_d → T_d × T_d
+    let iden = CommitNode::iden(context).unwrap();
+    // T_d × T_d → 2 × (T_d × T_d)
+    let pair_get_first_iden = CommitNode::pair(context, get_first, iden).unwrap();
+    // T_d × T_d → 2
+    let bit_true = CommitNode::bit_true(context).unwrap();
+    // 2 × (T_d × T_d) → 2
+    let cond_true_second = CommitNode::cond(context, bit_true, get_second).unwrap();
+
+    // T_d × T_d → 2
+    CommitNode::comp(context, pair_get_first_iden, cond_true_second).unwrap()
+}
+
+/// Set the tape symbol at the one-hot index to the given symbol, for tapes of `2^d` words.
+///
+/// Words without the index bit remain unchanged.
+///
+/// `set: 2 × (T_d × T_d) → T_d`
+fn set(context: &mut Context<Turing>, d: usize) -> Rc<CommitNode<Turing>> {
+    if d == 0 {
+        return Computation256::set(context);
+    }
+
+    // 2 → 2
+    let iden_2 = CommitNode::iden(context).unwrap();
+    // 2 × (T_d × T_d) → 2
+    let b = CommitNode::take(context, iden_2).unwrap();
+
+    // 2 × (T_(d - 1) × T_(d - 1)) → T_(d - 1)
+    let set_half = set(context, d - 1);
+    // T_d × T_d → T_(d - 1) × T_(d - 1)
+    let first_halves = halves(context, false);
+    // 2 × (T_d × T_d) → T_(d - 1) × T_(d - 1)
+    let drop_first_halves = CommitNode::drop(context, first_halves).unwrap();
+    // 2 × (T_d × T_d) → 2 × (T_(d - 1) × T_(d - 1))
+    let pair_b_first = CommitNode::pair(context, b.clone(), drop_first_halves).unwrap();
+    // 2 × (T_d × T_d) → T_(d - 1)
+    let set_first = CommitNode::comp(context, pair_b_first, set_half.clone()).unwrap();
+    // T_d × T_d → T_(d - 1) × T_(d - 1)
+    let second_halves = halves(context, true);
+    // 2 × (T_d × T_d) → T_(d - 1) × T_(d - 1)
+    let drop_second_halves = CommitNode::drop(context, second_halves).unwrap();
+    // 2 × (T_d × T_d) → 2 × (T_(d - 1) × T_(d - 1))
+    let pair_b_second = CommitNode::pair(context, b, drop_second_halves).unwrap();
+    // 2 × (T_d × T_d) → T_(d - 1)
+    let set_second = CommitNode::comp(context, pair_b_second, set_half).unwrap();
+
+    // 2 × (T_d × T_d) → T_d
+    CommitNode::pair(context, set_first, set_second).unwrap()
+}
+
+/// Check equality of two tapes (or indices) of `2^d` words.
+///
+/// `eq: T_d × T_d → 2`
+fn eq(context: &mut Context<Turing>, d: usize) -> Rc<CommitNode<Turing>> {
+    if d == 0 {
+        return Computation256::eq_tape(context);
+    }
+
+    // T_(d - 1) × T_(d - 1) → 2
+    let eq_half = eq(context, d - 1);
+    // T_d × T_d → T_(d - 1) × T_(d - 1)
+    let first_halves = halves(context, false);
+    // T_d × T_d → 2
+    let eq_first = CommitNode::comp(context, first_halves, eq_half.clone()).unwrap();
+    // T_d × T_d → T_(d - 1) × T_(d - 1)
+    let second_halves = halves(context, true);
+    // T_d × T_d → 2 × (T_(d - 1) × T_(d - 1))
+    let pair_eq_first_second = CommitNode::pair(context, eq_first, second_halves).unwrap();
+
+    // T_(d - 1) × T_(d - 1) → 2
+    let bit_false = CommitNode::bit_false(context).unwrap();
+    // 2 × (T_(d - 1) × T_(d - 1)) → 2
+    let cond_eq_second_false = CommitNode::cond(context, eq_half, bit_false).unwrap();
+
+    // T_d × T_d → 2
+    CommitNode::comp(context, pair_eq_first_second, cond_eq_second_false).unwrap()
+}
+
+/// Check if all `2^d` words are zero.
+///
+/// `is_zero: T_d → 2`
+fn is_zero(context: &mut Context<Turing>, d: usize) -> Rc<CommitNode<Turing>> {
+    if d == 0 {
+        return CommitNode::jet(context, &jet::turing::IS_ZERO256).unwrap();
+    }
+
+    // T_(d - 1) → 2
+    let is_zero_half = is_zero(context, d - 1);
+    // T_(d - 1) → T_(d - 1)
+    let iden_half = CommitNode::iden(context).unwrap();
+    // T_d → T_(d - 1)
+    let first = CommitNode::take(context, iden_half.clone()).unwrap();
+    // T_d → T_(d - 1)
+    let second = CommitNode::drop(context, iden_half).unwrap();
+    // T_d → 2
+    let is_zero_first = CommitNode::comp(context, first, is_zero_half.clone()).unwrap();
+    // T_d → 2 × T_(d - 1)
+    let pair_is_zero_first_second = CommitNode::pair(context, is_zero_first, second).unwrap();
+
+    // T_(d - 1) → 2
+    let bit_false = CommitNode::bit_false(context).unwrap();
+    // 2 × T_(d - 1) → 2
+    let cond_is_zero_second_false = CommitNode::cond(context, is_zero_half, bit_false).unwrap();
+
+    // T_d → 2
+    CommitNode::comp(
+        context,
+        pair_is_zero_first_second,
+        cond_is_zero_second_false,
+    )
+    .unwrap()
+}
+
+/// Check if exactly one bit of `2^d` words is set.
+///
+/// Either the first half is one-hot and the second half is zero, or the other way around.
+///
+/// `is_one_hot: T_d → 2`
+fn is_one_hot(context: &mut Context<Turing>, d: usize) -> Rc<CommitNode<Turing>> {
+    if d == 0 {
+        return CommitNode::jet(context, &jet::turing::IS_ONE_HOT_256).unwrap();
+    }
+
+    // T_(d - 1) → 2
+    let is_zero_half = is_zero(context, d - 1);
+    // T_(d - 1) → 2
+    let is_one_hot_half = is_one_hot(context, d - 1);
+    // T_(d - 1) → T_(d - 1)
+    let iden_half = CommitNode::iden(context).unwrap();
+    // T_d → T_(d - 1)
+    let first = CommitNode::take(context, iden_half.clone()).unwrap();
+    // T_d → T_(d - 1)
+    let second = CommitNode::drop(context, iden_half).unwrap();
+
+    // T_d → 2
+    let is_zero_first = CommitNode::comp(context, first.clone(), is_zero_half.clone()).unwrap();
+    // T_d → 2
+    let is_one_hot_first = CommitNode::comp(context, first, is_one_hot_half.clone()).unwrap();
+    // T_d → 2
+    let is_zero_second = CommitNode::comp(context, second.clone(), is_zero_half).unwrap();
+    // T_d → 2
+    let is_one_hot_second = CommitNode::comp(context, second, is_one_hot_half).unwrap();
+
+    // If the first half is not zero, then it must be one-hot and the second half must be zero
+    // T_d → T_d
+    let iden = CommitNode::iden(context).unwrap();
+    // T_d → 2 × T_d
+    let pair_is_one_hot_first_iden =
+        CommitNode::pair(context, is_one_hot_first, iden.clone()).unwrap();
+    // T_d → 2
+    let bit_false = CommitNode::bit_false(context).unwrap();
+    // 2 × T_d → 2
+    let cond_is_zero_second_false = CommitNode::cond(context, is_zero_second, bit_false).unwrap();
+    // T_d → 2
+    let first_one_hot_second_zero = CommitNode::comp(
+        context,
+        pair_is_one_hot_first_iden,
+        cond_is_zero_second_false,
+    )
+    .unwrap();
+
+    // T_d → 2 × T_d
+    let pair_is_zero_first_iden = CommitNode::pair(context, is_zero_first, iden).unwrap();
+    // 2 × T_d → 2
+    let cond_second_first =
+        CommitNode::cond(context, is_one_hot_second, first_one_hot_second_zero).unwrap();
+
+    // T_d → 2
+    CommitNode::comp(context, pair_is_zero_first_iden, cond_second_first).unwrap()
+}
+
+/// Check if the one-hot index of `2^d` words points to the first or last cell.
+///
+/// `is_edge: T_d → 2`
+fn is_edge(context: &mut Context<Turing>, d: usize, last: bool) -> Rc<CommitNode<Turing>> {
+    // T_d → T_d
+    let iden = CommitNode::iden(context).unwrap();
+    // T_d → T_d
+    let edge = CommitNode::scribe(context, &edge_value(d, last)).unwrap();
+    // T_d → T_d × T_d
+    let pair_iden_edge = CommitNode::pair(context, iden, edge).unwrap();
+    // T_d × T_d → 2
+    let eq = eq(context, d);
+
+    // T_d → 2
+    CommitNode::comp(context, pair_iden_edge, eq).unwrap()
+}
+
+/// Shift the one-hot index of `2^d` words by one cell.
+///
+/// The index moves to the right (towards the last cell) if `right` is true.
+/// Otherwise, it moves to the left (towards the first cell).
+///
+/// `shift: T_d → T_d`
+fn shift(context: &mut Context<Turing>, d: usize, right: bool) -> Rc<CommitNode<Turing>> {
+    if d == 0 {
+        return if right {
+            Computation256::inc_index(context)
+        } else {
+            Computation256::dec_index(context)
+        };
+    }
+
+    // The index crosses the boundary of the halves
+    // if it moves right from the last cell of the first half
+    // or if it moves left from the first cell of the second half.
+    // T_(d - 1) → 2
+    let is_edge_half = is_edge(context, d - 1, right);
+    // T_(d - 1) → T_(d - 1)
+    let iden_half = CommitNode::iden(context).unwrap();
+    // T_d → 2
+    let is_crossing = if right {
+        let first = CommitNode::take(context, iden_half).unwrap();
+        CommitNode::comp(context, first, is_edge_half).unwrap()
+    } else {
+        let second = CommitNode::drop(context, iden_half).unwrap();
+        CommitNode::comp(context, second, is_edge_half).unwrap()
+    };
+    // T_d → T_d
+    let iden = CommitNode::iden(context).unwrap();
+    // T_d → 2 × T_d
+    let pair_is_crossing_iden = CommitNode::pair(context, is_crossing, iden).unwrap();
+
+    // T_d → T_d
+    let crossed = CommitNode::scribe(context, &crossed_value(d, right)).unwrap();
+
+    // T_(d - 1) → T_(d - 1)
+    let shift_half = shift(context, d - 1, right);
+    // T_(d - 1) → T_(d - 1)
+    let iden_half = CommitNode::iden(context).unwrap();
+    // T_d → T_(d - 1)
+    let first = CommitNode::take(context, iden_half.clone()).unwrap();
+    // T_d → T_(d - 1)
+    let second = CommitNode::drop(context, iden_half).unwrap();
+    // T_d → T_(d - 1)
+    let shift_first = CommitNode::comp(context, first, shift_half.clone()).unwrap();
+    // T_d → T_(d - 1)
+    let shift_second = CommitNode::comp(context, second, shift_half).unwrap();
+    // T_d → T_d
+    let shift_halves = CommitNode::pair(context, shift_first, shift_second).unwrap();
+
+    // 2 × T_d → T_d
+    let cond_crossed_shift = CommitNode::cond(context, crossed, shift_halves).unwrap();
+
+    // T_d → T_d
+    CommitNode::comp(context, pair_is_crossing_iden, cond_crossed_shift).unwrap()
+}
+
+/// Return the value of the one-hot index of `2^d` words right after crossing the boundary of the halves.
+fn crossed_value(d: usize, right: bool) -> Value {
+    let zero = util::words_value(std::iter::repeat_n(&[0; 32], 1 << (d - 1)));
+
+    if right {
+        Value::prod(zero, edge_value(d - 1, false))
+    } else {
+        Value::prod(edge_value(d - 1, true), zero)
+    }
+}
+
+/// Increment the one-hot index of `2^d` words.
+///
+/// `inc_index: T_d → T_d`
+fn inc_index(context: &mut Context<Turing>, d: usize) -> Rc<CommitNode<Turing>> {
+    shift(context, d, true)
+}
+
+/// Decrement the one-hot index of `2^d` words.
+///
+/// `dec_index: T_d → T_d`
+fn dec_index(context: &mut Context<Turing>, d: usize) -> Rc<CommitNode<Turing>> {
+    shift(context, d, false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulator::one_hot;
+    use simplicity::exec::BitMachine;
+
+    fn value_512(bytes: &[u8; 64]) -> Value {
+        util::words_value(bytes.chunks(32).map(|word| word.try_into().unwrap()))
+    }
+
+    const CELLS: [usize; 6] = [0, 1, 255, 256, 300, 511];
+
+    #[test]
+    fn get_set() {
+        let mut context = Context::default();
+        let get = Computation512::get(&mut context)
+            .finalize(std::iter::empty())
+            .unwrap();
+        let set = Computation512::set(&mut context)
+            .finalize(std::iter::empty())
+            .unwrap();
+
+        for written in CELLS {
+            let w = one_hot::<64>(written);
+
+            for cell in CELLS {
+                let i = value_512(&one_hot::<64>(cell));
+
+                let mut mac = BitMachine::for_program(&get);
+                mac.input(&Value::prod(value_512(&w), i.clone()));
+                let output = mac.exec(&get, &()).unwrap();
+                assert_eq!(Value::u1((written == cell) as u8), output);
+
+                let mut expected = w;
+                expected[cell / 8] |= 0b10000000 >> (cell % 8);
+                let mut mac = BitMachine::for_program(&set);
+                mac.input(&Value::prod(Value::u1(1), Value::prod(value_512(&w), i)));
+                let output = mac.exec(&set, &()).unwrap();
+                assert_eq!(value_512(&expected), output);
+            }
+        }
+    }
+
+    #[test]
+    fn inc_dec_index() {
+        let mut context = Context::default();
+        let inc = Computation1024::inc_index(&mut context)
+            .finalize(std::iter::empty())
+            .unwrap();
+        let dec = Computation1024::dec_index(&mut context)
+            .finalize(std::iter::empty())
+            .unwrap();
+        let one_hot_1024 = |cell: usize| {
+            let index = one_hot::<128>(cell);
+            util::words_value(index.chunks(32).map(|word| word.try_into().unwrap()))
+        };
+
+        for cell in [0, 100, 255, 256, 511, 512, 767, 768, 1000] {
+            let input = one_hot_1024(cell);
+
+            if cell < 1023 {
+                let mut mac = BitMachine::for_program(&inc);
+                mac.input(&input);
+                let output = mac.exec(&inc, &()).unwrap();
+                assert_eq!(one_hot_1024(cell + 1), output);
+            }
+
+            if cell > 0 {
+                let mut mac = BitMachine::for_program(&dec);
+                mac.input(&input);
+                let output = mac.exec(&dec, &()).unwrap();
+                assert_eq!(one_hot_1024(cell - 1), output);
+            }
+        }
+    }
+
+    #[test]
+    fn is_valid_index() {
+        let mut context = Context::default();
+        let program = Computation1024::is_valid_index(&mut context)
+            .finalize(std::iter::empty())
+            .unwrap();
+        let value_1024 = |index: &[u8; 128]| {
+            util::words_value(index.chunks(32).map(|word| word.try_into().unwrap()))
+        };
+
+        let mut index_valid = vec![([0; 128], false)];
+        for cell in [0, 255, 256, 600, 1023] {
+            index_valid.push((one_hot::<128>(cell), true));
+            for other in [0, 300, 1023] {
+                if other != cell {
+                    let mut index = one_hot::<128>(cell);
+                    index[other / 8] |= one_hot::<128>(other)[other / 8];
+                    index_valid.push((index, false));
+                }
+            }
+        }
+
+        for (index, valid) in index_valid {
+            let mut mac = BitMachine::for_program(&program);
+            mac.input(&value_1024(&index));
+            let output = mac.exec(&program, &()).unwrap();
+            assert_eq!(Value::u1(valid as u8), output);
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::jet;
 use crate::jet::turing::TuringJetName;
+use bitcoin_hashes::{sha256, Hash, HashEngine};
 use simplicity::bititer::BitIter;
 use simplicity::bitwriter::BitWriter;
 use simplicity::exec::BitMachine;
@@ -11,11 +12,21 @@ use std::io::Write;
 pub struct Turing;
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
-pub enum TuringError {}
+pub enum TuringError {
+    /// The one-hot index was shifted left from the first cell of the tape
+    HeadOffLeftEdge,
+    /// The one-hot index was shifted right from the last cell of the tape
+    HeadOffRightEdge,
+}
 
 impl std::fmt::Display for TuringError {
-    fn fmt(&self, _f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match *self {}
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            TuringError::HeadOffLeftEdge => write!(f, "Cursor moved off the left edge of the tape"),
+            TuringError::HeadOffRightEdge => {
+                write!(f, "Cursor moved off the right edge of the tape")
+            }
+        }
     }
 }
 
@@ -46,6 +57,11 @@ impl Application for Turing {
             4 => Ok(&jet::turing::AND_256),
             5 => Ok(&jet::turing::OR_256),
             6 => Ok(&jet::turing::IS_ZERO256),
+            7 => Ok(&jet::turing::IS_ONE_HOT_256),
+            8 => Ok(&jet::turing::SHA256_PAIR),
+            9 => Ok(&jet::turing::POPCOUNT_256),
+            10 => Ok(&jet::turing::POPCOUNT_512),
+            11 => Ok(&jet::turing::POPCOUNT_1024),
             _ => Err(Error::ParseError("Illegal jet encoding")),
         }
     }
@@ -59,6 +75,11 @@ impl Application for Turing {
             TuringJetName::And256 => w.write_bits_be(128 + 4, 8),
             TuringJetName::Or256 => w.write_bits_be(128 + 5, 8),
             TuringJetName::IsZero256 => w.write_bits_be(128 + 6, 8),
+            TuringJetName::IsOneHot256 => w.write_bits_be(128 + 7, 8),
+            TuringJetName::Sha256Pair => w.write_bits_be(128 + 8, 8),
+            TuringJetName::PopCount256 => w.write_bits_be(128 + 9, 8),
+            TuringJetName::PopCount512 => w.write_bits_be(128 + 10, 8),
+            TuringJetName::PopCount1024 => w.write_bits_be(128 + 11, 8),
         }
     }
 
@@ -75,12 +96,12 @@ impl Application for Turing {
             }
             TuringJetName::RightShift256 => {
                 let input = mac.read_32bytes();
-                let shifted = right_shift_256(&input);
+                let shifted = right_shift_256(&input)?;
                 mac.write_bytes(&shifted);
             }
             TuringJetName::LeftShift256 => {
                 let input = mac.read_32bytes();
-                let shifted = left_shift_256(&input);
+                let shifted = left_shift_256(&input)?;
                 mac.write_bytes(&shifted);
             }
             TuringJetName::Complement256 => {
@@ -119,13 +140,40 @@ impl Application for Turing {
                 let a = mac.read_32bytes();
                 mac.write_bit(a.eq(&[0; 32]));
             }
+            TuringJetName::IsOneHot256 => {
+                let a = mac.read_32bytes();
+                mac.write_bit(is_one_hot_256(&a));
+            }
+            TuringJetName::Sha256Pair => {
+                let a = mac.read_32bytes();
+                let b = mac.read_32bytes();
+                mac.write_bytes(&sha256_pair(&a, &b));
+            }
+            TuringJetName::PopCount256 => {
+                let a = mac.read_32bytes();
+                mac.write_bytes(&popcount(&[a]).to_be_bytes());
+            }
+            TuringJetName::PopCount512 => {
+                let a = mac.read_32bytes();
+                let b = mac.read_32bytes();
+                mac.write_bytes(&popcount(&[a, b]).to_be_bytes());
+            }
+            TuringJetName::PopCount1024 => {
+                let mut words = [[0; 32]; 4];
+
+                for word in &mut words {
+                    *word = mac.read_32bytes();
+                }
+
+                mac.write_bytes(&popcount(&words).to_be_bytes());
+            }
         }
 
         Ok(())
     }
 }
 
-fn right_shift_256(input: &[u8; 32]) -> [u8; 32] {
+fn right_shift_256(input: &[u8; 32]) -> Result<[u8; 32], TuringError> {
     let mut shifted = [0; 32];
 
     for (byte_index, byte) in input.iter().enumerate() {
@@ -136,18 +184,19 @@ fn right_shift_256(input: &[u8; 32]) -> [u8; 32] {
 
         if bit_index < 7 {
             shifted[byte_index] = one_hot_of(bit_index + 1);
-        } else {
-            // Panics if last bit in last byte is shifted right
+        } else if byte_index < 31 {
             shifted[byte_index + 1] = one_hot_of(0);
+        } else {
+            return Err(TuringError::HeadOffRightEdge);
         }
 
         break;
     }
 
-    shifted
+    Ok(shifted)
 }
 
-fn left_shift_256(input: &[u8; 32]) -> [u8; 32] {
+fn left_shift_256(input: &[u8; 32]) -> Result<[u8; 32], TuringError> {
     let mut shifted = [0; 32];
 
     for (byte_index, byte) in input.iter().enumerate() {
@@ -158,15 +207,37 @@ fn left_shift_256(input: &[u8; 32]) -> [u8; 32] {
 
         if bit_index > 0 {
             shifted[byte_index] = one_hot_of(bit_index - 1);
-        } else {
-            // Panics if first bit in first byte is shifted left
+        } else if byte_index > 0 {
             shifted[byte_index - 1] = one_hot_of(7);
+        } else {
+            return Err(TuringError::HeadOffLeftEdge);
         }
 
         break;
     }
 
-    shifted
+    Ok(shifted)
+}
+
+fn is_one_hot_256(input: &[u8; 32]) -> bool {
+    input.iter().map(|byte| byte.count_ones()).sum::<u32>() == 1
+}
+
+/// Return the SHA-256 hash of the concatenation of the given 256-bit words.
+pub(crate) fn sha256_pair(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let mut engine = sha256::Hash::engine();
+    engine.input(a);
+    engine.input(b);
+    sha256::Hash::from_engine(engine).into_inner()
+}
+
+/// Return the number of set bits in the given 256-bit words.
+fn popcount(words: &[[u8; 32]]) -> u16 {
+    words
+        .iter()
+        .flatten()
+        .map(|byte| byte.count_ones() as u16)
+        .sum()
 }
 
 fn one_hot_get_index(byte: u8) -> Option<usize> {
@@ -199,6 +270,8 @@ fn one_hot_of(index: usize) -> u8 {
 
 #[cfg(test)]
 mod tests {
+    use super::TuringError;
+
     #[test]
     fn right_shift_256() {
         let base = [
@@ -217,18 +290,25 @@ mod tests {
         ];
 
         for (input_byte, output_byte, carry) in input_output_carry {
-            let mut input = base.clone();
+            let mut input = base;
             input[0] = input_byte;
-            let mut expected = base.clone();
+            let mut expected = base;
             if carry {
                 expected[1] = output_byte
             } else {
                 expected[0] = output_byte
             };
 
-            let output = super::right_shift_256(&input);
+            let output = super::right_shift_256(&input).unwrap();
             assert_eq!(expected, output);
         }
+
+        let mut input = base;
+        input[31] = 0b00000001;
+        assert_eq!(
+            Err(TuringError::HeadOffRightEdge),
+            super::right_shift_256(&input)
+        );
     }
 
     #[test]
@@ -249,17 +329,70 @@ mod tests {
         ];
 
         for (input_byte, output_byte, carry) in input_output_carry {
-            let mut input = base.clone();
+            let mut input = base;
             input[1] = input_byte;
-            let mut expected = base.clone();
+            let mut expected = base;
             if carry {
                 expected[0] = output_byte
             } else {
                 expected[1] = output_byte
             };
 
-            let output = super::left_shift_256(&input);
+            let output = super::left_shift_256(&input).unwrap();
             assert_eq!(expected, output);
         }
+
+        let mut input = base;
+        input[0] = 0b10000000;
+        assert_eq!(
+            Err(TuringError::HeadOffLeftEdge),
+            super::left_shift_256(&input)
+        );
+    }
+
+    #[test]
+    fn is_one_hot_256() {
+        let mut input = [0; 32];
+        assert!(!super::is_one_hot_256(&input));
+
+        for byte_index in [0, 17, 31] {
+            for bit_index in 0..8 {
+                let mut input = [0; 32];
+                input[byte_index] = super::one_hot_of(bit_index);
+                assert!(super::is_one_hot_256(&input));
+            }
+        }
+
+        input[0] = 0b10000001;
+        assert!(!super::is_one_hot_256(&input));
+        input[0] = 0b10000000;
+        input[31] = 0b00000001;
+        assert!(!super::is_one_hot_256(&input));
+    }
+
+    #[test]
+    fn sha256_pair() {
+        let expected = [
+            0xf5, 0xa5, 0xfd, 0x42, 0xd1, 0x6a, 0x20, 0x30, 0x27, 0x98, 0xef, 0x6e, 0xd3, 0x09,
+            0x97, 0x9b, 0x43, 0x00, 0x3d, 0x23, 0x20, 0xd9, 0xf0, 0xe8, 0xea, 0x98, 0x31, 0xa9,
+            0x27, 0x59, 0xfb, 0x4b,
+        ];
+        assert_eq!(expected, super::sha256_pair(&[0; 32], &[0; 32]));
+        assert_ne!(
+            super::sha256_pair(&[0; 32], &[1; 32]),
+            super::sha256_pair(&[1; 32], &[0; 32])
+        );
+    }
+
+    #[test]
+    fn popcount() {
+        assert_eq!(0, super::popcount(&[[0; 32]]));
+        assert_eq!(256, super::popcount(&[[0xff; 32]]));
+        assert_eq!(1024, super::popcount(&[[0xff; 32]; 4]));
+
+        let mut input = [[0; 32]; 2];
+        input[0][0] = 0b10000001;
+        input[1][31] = 0b01110000;
+        assert_eq!(5, super::popcount(&input));
     }
 }
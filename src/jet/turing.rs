@@ -13,6 +13,11 @@ pub enum TuringJetName {
     And256,
     Or256,
     IsZero256,
+    IsOneHot256,
+    Sha256Pair,
+    PopCount256,
+    PopCount512,
+    PopCount1024,
 }
 
 pub const EQ256: JetNode<Turing> = JetNode {
@@ -91,3 +96,58 @@ pub const IS_ZERO256: JetNode<Turing> = JetNode {
     source_ty: TypeName(b"h"),
     target_ty: TypeName(b"2"),
 };
+
+pub const IS_ONE_HOT_256: JetNode<Turing> = JetNode {
+    name: TuringJetName::IsOneHot256,
+    cmr: Cmr(Midstate([
+        0x7e, 0x2e, 0x2a, 0xac, 0xfd, 0x1e, 0x09, 0x40, 0x36, 0x7a, 0x4d, 0x63, 0xc9, 0xe6, 0x96,
+        0x5e, 0x49, 0xbf, 0x3f, 0x48, 0xf5, 0x1b, 0x44, 0xaf, 0xa6, 0x49, 0xad, 0x45, 0xfb, 0x7a,
+        0xdc, 0x04,
+    ])),
+    source_ty: TypeName(b"h"),
+    target_ty: TypeName(b"2"),
+};
+
+pub const SHA256_PAIR: JetNode<Turing> = JetNode {
+    name: TuringJetName::Sha256Pair,
+    cmr: Cmr(Midstate([
+        0x9b, 0x5a, 0xb0, 0xcb, 0x4d, 0x39, 0x6b, 0x60, 0xac, 0x57, 0x5a, 0x7d, 0xd6, 0xa2, 0xd7,
+        0xea, 0xb1, 0x33, 0x99, 0xe8, 0xb3, 0x24, 0x4b, 0xb4, 0xed, 0x9e, 0x90, 0x4d, 0x12, 0x05,
+        0x6b, 0xea,
+    ])),
+    source_ty: TypeName(b"*hh"),
+    target_ty: TypeName(b"h"),
+};
+
+pub const POPCOUNT_256: JetNode<Turing> = JetNode {
+    name: TuringJetName::PopCount256,
+    cmr: Cmr(Midstate([
+        0x4e, 0xe1, 0x1b, 0x7d, 0x96, 0xb1, 0xf4, 0x39, 0x86, 0xda, 0x57, 0x9d, 0xee, 0xd2, 0xfc,
+        0xa9, 0x23, 0x3e, 0xfb, 0xba, 0x35, 0x4d, 0xa3, 0x48, 0xe9, 0xa3, 0xf5, 0xd9, 0x9d, 0x91,
+        0x33, 0x1b,
+    ])),
+    source_ty: TypeName(b"h"),
+    target_ty: TypeName(b"s"),
+};
+
+pub const POPCOUNT_512: JetNode<Turing> = JetNode {
+    name: TuringJetName::PopCount512,
+    cmr: Cmr(Midstate([
+        0x22, 0xdc, 0xc6, 0xc6, 0x24, 0x08, 0xec, 0x98, 0xd8, 0xe3, 0x93, 0x4c, 0x4e, 0xe8, 0x53,
+        0x17, 0x68, 0x2f, 0x1f, 0xa2, 0xe1, 0xb4, 0x50, 0x9c, 0x0e, 0x26, 0xc5, 0xe3, 0x7f, 0x77,
+        0x5a, 0x5d,
+    ])),
+    source_ty: TypeName(b"*hh"),
+    target_ty: TypeName(b"s"),
+};
+
+pub const POPCOUNT_1024: JetNode<Turing> = JetNode {
+    name: TuringJetName::PopCount1024,
+    cmr: Cmr(Midstate([
+        0xef, 0x88, 0x94, 0x56, 0x6b, 0x1c, 0x87, 0x6a, 0x44, 0x1f, 0xbc, 0x36, 0xc5, 0xe0, 0x81,
+        0x3d, 0xe2, 0x9b, 0x6a, 0xb0, 0xf3, 0x73, 0x03, 0xfb, 0xf4, 0x81, 0x48, 0xda, 0x73, 0x44,
+        0x4c, 0xb3,
+    ])),
+    source_ty: TypeName(b"**hh*hh"),
+    target_ty: TypeName(b"s"),
+};
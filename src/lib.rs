@@ -1,7 +1,11 @@
 pub mod computation;
 pub mod jet;
 pub mod machine;
+pub mod simulator;
 pub mod util;
 mod verifier;
 
-pub use verifier::Verifier;
+pub use verifier::{
+    execute, execute_output, DynVerifier, MultiTapeVerifier, StepError, TranslatedCycle,
+    TranslatedCycleVerifier, VerificationError, Verifier,
+};
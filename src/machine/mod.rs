@@ -1,11 +1,20 @@
+mod multi;
+mod nondeterministic;
+mod parse;
+mod table;
 mod three_beavers;
 mod trivial;
 mod two_beavers;
 
 use crate::jet::application::Turing;
+pub use multi::{MultiTapeMachine, MultiTapeTableMachine, MultiTapeTransition};
+pub use nondeterministic::NondeterministicTableMachine;
+pub use parse::ParseError;
 use simplicity::core::Context;
 use simplicity::CommitNode;
+use std::marker::PhantomData;
 use std::rc::Rc;
+pub use table::{Direction, TableMachine, Transition};
 pub use three_beavers::ThreeBeavers;
 pub use trivial::Trivial;
 pub use two_beavers::TwoBeavers;
@@ -13,25 +22,58 @@ pub use two_beavers::TwoBeavers;
 /// Definition of a Turing Machine.
 /// This includes the transition function
 /// _(where to move the cursor, which symbol to write and which state to go to, upon which input)_,
-/// as well as the initial and accepting state.
+/// as well as the initial and accepting state (and optionally the rejecting state).
 ///
 /// States are encoded as `m`-bit strings.
+/// Tape symbols are encoded as `s`-bit strings, where `s = 1` for the binary alphabet.
+/// The symbols must be encoded in the same way as by the [`crate::computation::Computation`].
 pub trait Machine {
     /// Go left, given the read tape symbol and current state?
     ///
-    /// `left: 2 × 2^m → 2`
+    /// `left: 2^s × 2^m → 2`
     fn left(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
 
+    /// Stay in place, given the read tape symbol and current state?
+    ///
+    /// Stay moves take precedence over [`Machine::left`].
+    /// Return `None` if the machine never stays in place, which is the default.
+    ///
+    /// `stay: 2^s × 2^m → 2`
+    fn stay(_context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        None
+    }
+
     /// Successor state, given the read tape symbol and current state.
     ///
-    /// `state: 2 × 2^m → 2^m`
+    /// `state: 2^s × 2^m → 2^m`
     fn state(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
 
     /// Tape symbol to write, given the read tape symbol and current state.
     ///
-    /// `write: 2 × 2^m → 2`
+    /// `write: 2^s × 2^m → 2^s`
     fn write(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
 
+    /// Check if the transition is allowed, given the read tape symbol and current state.
+    ///
+    /// Nondeterministic machines encode the choice of transition in the state
+    /// and only allow some choices. See [`NondeterministicTableMachine`].
+    /// Return `None` if every transition is allowed, which is the default.
+    ///
+    /// `allowed: 2^s × 2^m → 2`
+    fn allowed(_context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        None
+    }
+
+    /// Check if the transition is undefined, given the read tape symbol and current state.
+    ///
+    /// Machines with a partial transition function halt upon undefined transitions.
+    /// Return `None` if the transition function is total, which is the default.
+    ///
+    /// `halts: 2^s × 2^m → 2`
+    fn halts(_context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        None
+    }
+
     /// Check if the given state is the initial state.
     ///
     /// `initial: 2^m → 2`
@@ -42,8 +84,138 @@ pub trait Machine {
     /// `accepting: 2^m → 2`
     fn accepting(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
 
+    /// Check if the given state is the rejecting state.
+    ///
+    /// Deciders halt in the accepting state upon inputs in their language and in the rejecting state otherwise.
+    /// Return `None` if the machine never rejects explicitly, which is the default.
+    ///
+    /// `rejecting: 2^m → 2`
+    fn rejecting(_context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        None
+    }
+
     /// Check equality of two states
     ///
     /// `eq_state: 2^m × 2^m → 2`
     fn eq_state(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
 }
+
+/// Definition of a Turing Machine that is known at runtime.
+///
+/// This is the variant of [`Machine`] that takes `&self`,
+/// so that machines can be loaded from files or command-line arguments.
+/// Every [`Machine`] `M` is available at runtime as `PhantomData<M>`.
+pub trait DynMachine {
+    /// Go left, given the read tape symbol and current state?
+    ///
+    /// `left: 2^s × 2^m → 2`
+    fn left(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
+
+    /// Stay in place, given the read tape symbol and current state?
+    ///
+    /// Stay moves take precedence over [`DynMachine::left`].
+    /// Return `None` if the machine never stays in place, which is the default.
+    ///
+    /// `stay: 2^s × 2^m → 2`
+    fn stay(&self, _context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        None
+    }
+
+    /// Successor state, given the read tape symbol and current state.
+    ///
+    /// `state: 2^s × 2^m → 2^m`
+    fn state(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
+
+    /// Tape symbol to write, given the read tape symbol and current state.
+    ///
+    /// `write: 2^s × 2^m → 2^s`
+    fn write(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
+
+    /// Check if the transition is allowed, given the read tape symbol and current state.
+    ///
+    /// Nondeterministic machines encode the choice of transition in the state
+    /// and only allow some choices. See [`NondeterministicTableMachine`].
+    /// Return `None` if every transition is allowed, which is the default.
+    ///
+    /// `allowed: 2^s × 2^m → 2`
+    fn allowed(&self, _context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        None
+    }
+
+    /// Check if the transition is undefined, given the read tape symbol and current state.
+    ///
+    /// Machines with a partial transition function halt upon undefined transitions.
+    /// Return `None` if the transition function is total, which is the default.
+    ///
+    /// `halts: 2^s × 2^m → 2`
+    fn halts(&self, _context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        None
+    }
+
+    /// Check if the given state is the initial state.
+    ///
+    /// `initial: 2^m → 2`
+    fn initial(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
+
+    /// Check if the given state is the accepting state.
+    ///
+    /// `accepting: 2^m → 2`
+    fn accepting(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
+
+    /// Check if the given state is the rejecting state.
+    ///
+    /// Deciders halt in the accepting state upon inputs in their language and in the rejecting state otherwise.
+    /// Return `None` if the machine never rejects explicitly, which is the default.
+    ///
+    /// `rejecting: 2^m → 2`
+    fn rejecting(&self, _context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        None
+    }
+
+    /// Check equality of two states
+    ///
+    /// `eq_state: 2^m × 2^m → 2`
+    fn eq_state(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
+}
+
+impl<M: Machine> DynMachine for PhantomData<M> {
+    fn left(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        M::left(context)
+    }
+
+    fn stay(&self, context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        M::stay(context)
+    }
+
+    fn state(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        M::state(context)
+    }
+
+    fn write(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        M::write(context)
+    }
+
+    fn allowed(&self, context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        M::allowed(context)
+    }
+
+    fn halts(&self, context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        M::halts(context)
+    }
+
+    fn initial(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        M::initial(context)
+    }
+
+    fn accepting(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        M::accepting(context)
+    }
+
+    fn rejecting(&self, context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        M::rejecting(context)
+    }
+
+    fn eq_state(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        M::eq_state(context)
+    }
+}
@@ -0,0 +1,515 @@
+use crate::jet::application::Turing;
+use crate::machine::table::{
+    check_n_states, check_transition, encoding_bits, lookup, select, state_flags, state_leaves,
+};
+use crate::machine::Direction;
+use crate::util;
+use simplicity::core::{Context, Value};
+use simplicity::CommitNode;
+use std::rc::Rc;
+
+/// Definition of a Turing Machine with `k` tapes that is known at runtime.
+///
+/// The machine reads the symbols under all `k` heads at once,
+/// writes one symbol to each tape and moves each head independently.
+///
+/// States are encoded as `m`-bit strings and tape symbols as `s`-bit strings, as in [`super::Machine`].
+/// The read symbols are nested to the right: `S = 2^s × (2^s × … × 2^s)` with `k` factors,
+/// starting with the symbol of the first tape. For `k = 1`, `S = 2^s`.
+pub trait MultiTapeMachine {
+    /// Return the number of tapes `k`.
+    fn n_tapes(&self) -> usize;
+
+    /// Move the head of the given tape left, given the read tape symbols and current state?
+    ///
+    /// `left: S × 2^m → 2`
+    fn left(&self, context: &mut Context<Turing>, tape: usize) -> Rc<CommitNode<Turing>>;
+
+    /// Leave the head of the given tape in place, given the read tape symbols and current state?
+    ///
+    /// Stay moves take precedence over [`MultiTapeMachine::left`].
+    /// Return `None` if the head never stays in place.
+    ///
+    /// `stay: S × 2^m → 2`
+    fn stay(&self, context: &mut Context<Turing>, tape: usize) -> Option<Rc<CommitNode<Turing>>>;
+
+    /// Successor state, given the read tape symbols and current state.
+    ///
+    /// `state: S × 2^m → 2^m`
+    fn state(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
+
+    /// Tape symbol to write to the given tape, given the read tape symbols and current state.
+    ///
+    /// `write: S × 2^m → 2^s`
+    fn write(&self, context: &mut Context<Turing>, tape: usize) -> Rc<CommitNode<Turing>>;
+
+    /// Check if the transition is undefined, given the read tape symbols and current state.
+    ///
+    /// Return `None` if the transition function is total, which is the default.
+    ///
+    /// `halts: S × 2^m → 2`
+    fn halts(&self, _context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        None
+    }
+
+    /// Check if the given state is the initial state.
+    ///
+    /// `initial: 2^m → 2`
+    fn initial(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
+
+    /// Check if the given state is the accepting state.
+    ///
+    /// `accepting: 2^m → 2`
+    fn accepting(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
+
+    /// Check if the given state is the rejecting state.
+    ///
+    /// Return `None` if the machine never rejects explicitly, which is the default.
+    ///
+    /// `rejecting: 2^m → 2`
+    fn rejecting(&self, _context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        None
+    }
+
+    /// Check equality of two states
+    ///
+    /// `eq_state: 2^m × 2^m → 2`
+    fn eq_state(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
+}
+
+/// Entry of a multi-tape transition table:
+/// which symbols to write, where to move the heads and which state to go to.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct MultiTapeTransition {
+    /// Tape symbol to write, for each tape
+    pub write: Vec<u8>,
+    /// Direction in which to move the head, for each tape
+    pub directions: Vec<Direction>,
+    /// Successor state
+    pub next: usize,
+}
+
+impl MultiTapeTransition {
+    /// Create a new transition.
+    pub fn new(write: Vec<u8>, directions: Vec<Direction>, next: usize) -> Self {
+        Self {
+            write,
+            directions,
+            next,
+        }
+    }
+}
+
+/// Turing machine with `k` tapes that is given by a transition table.
+///
+/// The table assigns to each state `q` and symbols `b_0, …, b_(k - 1)` read from the tapes
+/// a [`MultiTapeTransition`].
+/// The Simplicity expressions of the machine are synthesized from the table,
+/// as for [`super::TableMachine`], which this machine equals for `k = 1`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct MultiTapeTableMachine {
+    n_tapes: usize,
+    n_symbols: usize,
+    transitions: Vec<Vec<MultiTapeTransition>>,
+    initial: Vec<bool>,
+    accepting: Vec<bool>,
+    rejecting: Vec<bool>,
+}
+
+impl MultiTapeTableMachine {
+    /// Create a machine with `n_tapes` tapes from its transition table and its initial and accepting states.
+    ///
+    /// The transition of state `q` upon reading symbols `b_0, …, b_(k - 1)` is `transitions[q][r]`,
+    /// where `r` is the number with digits `b_0 … b_(k - 1)` in base `n_symbols`
+    /// _(the first tape is most significant)_.
+    /// Every state must have a transition for each of the `n_symbols^k` combinations of read symbols.
+    pub fn new(
+        n_tapes: usize,
+        transitions: Vec<Vec<MultiTapeTransition>>,
+        initial: &[usize],
+        accepting: &[usize],
+    ) -> Self {
+        assert!(n_tapes > 0, "There must be at least one tape");
+        let n_states = transitions.len();
+        check_n_states(n_states);
+
+        let n_combinations = transitions[0].len();
+        let n_symbols = (2..=256)
+            .find(|n: &usize| n.checked_pow(n_tapes as u32) == Some(n_combinations))
+            .expect("There must be a transition for each combination of (at least two) symbols");
+
+        for state_transitions in &transitions {
+            assert_eq!(
+                n_combinations,
+                state_transitions.len(),
+                "Every state must have a transition for each combination of symbols"
+            );
+        }
+
+        for transition in transitions.iter().flatten() {
+            assert_eq!(
+                n_tapes,
+                transition.write.len(),
+                "Every transition must write to each tape"
+            );
+            assert_eq!(
+                n_tapes,
+                transition.directions.len(),
+                "Every transition must move each head"
+            );
+            check_transition(n_states, n_symbols, transition.next, &transition.write);
+        }
+
+        let initial_states = state_flags(n_states, initial, "Initial");
+        let accepting_states = state_flags(n_states, accepting, "Accepting");
+
+        Self {
+            n_tapes,
+            n_symbols,
+            transitions,
+            initial: initial_states,
+            accepting: accepting_states,
+            rejecting: vec![false; n_states],
+        }
+    }
+
+    /// Make the given states rejecting.
+    ///
+    /// Rejecting states must not be accepting.
+    pub fn with_rejecting(mut self, rejecting: &[usize]) -> Self {
+        for &q in rejecting {
+            assert!(q < self.n_states(), "Rejecting state {} does not exist", q);
+            assert!(!self.accepting[q], "State {} is already accepting", q);
+            self.rejecting[q] = true;
+        }
+        self
+    }
+
+    /// Return the number of tapes.
+    pub fn n_tapes(&self) -> usize {
+        self.n_tapes
+    }
+
+    /// Return the number of states.
+    pub fn n_states(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// Return the number of bits `m` in the state encoding.
+    pub fn state_bits(&self) -> usize {
+        encoding_bits(self.n_states())
+    }
+
+    /// Return the number of tape symbols.
+    pub fn n_symbols(&self) -> usize {
+        self.n_symbols
+    }
+
+    /// Return the number of bits `s` in the symbol encoding.
+    pub fn symbol_bits(&self) -> usize {
+        encoding_bits(self.n_symbols())
+    }
+
+    /// Return the transition of state `q` upon reading the given symbols, one for each tape.
+    pub fn transition(&self, q: usize, read: &[u8]) -> &MultiTapeTransition {
+        assert_eq!(self.n_tapes, read.len(), "Expected one symbol per tape");
+        let r = read.iter().fold(0, |r, &b| r * self.n_symbols + b as usize);
+        &self.transitions[q][r]
+    }
+
+    /// Check if the given state is an initial state.
+    pub fn is_initial(&self, q: usize) -> bool {
+        self.initial[q]
+    }
+
+    /// Check if the given state is an accepting state.
+    pub fn is_accepting(&self, q: usize) -> bool {
+        self.accepting[q]
+    }
+
+    /// Check if the given state is a rejecting state.
+    pub fn is_rejecting(&self, q: usize) -> bool {
+        self.rejecting[q]
+    }
+
+    /// Encode the given state as Simplicity value of type `2^m`.
+    pub fn state_value(&self, q: usize) -> Value {
+        util::bits_value(q, self.state_bits())
+    }
+
+    /// Encode the given tape symbol as Simplicity value of type `2^s`.
+    pub fn symbol_value(&self, b: u8) -> Value {
+        util::bits_value(b as usize, self.symbol_bits())
+    }
+
+    /// Encode the given symbols, one for each tape, as Simplicity value of type `S`.
+    pub fn symbols_value(&self, read: &[u8]) -> Value {
+        assert_eq!(self.n_tapes, read.len(), "Expected one symbol per tape");
+        let mut values = read.iter().rev().map(|&b| self.symbol_value(b));
+        let last = values.next().unwrap();
+
+        values.fold(last, |rest, b| Value::prod(b, rest))
+    }
+
+    /// Return an expression that maps the read tape symbols and current state
+    /// to the value of `f` for the corresponding transition.
+    ///
+    /// Encodings that don't correspond to any symbol are treated like the symbol `0`.
+    ///
+    /// `S × 2^m → A`
+    fn transition_expression<F: Fn(&MultiTapeTransition) -> Value>(
+        &self,
+        context: &mut Context<Turing>,
+        f: &F,
+    ) -> Rc<CommitNode<Turing>> {
+        self.symbols_expression(context, self.n_tapes, 0, f)
+    }
+
+    /// Return an expression that maps the symbols read from the last `n_tapes` tapes and current state
+    /// to the value of `f` for the corresponding transition,
+    /// where `prefix` is the combination of symbols read from the other tapes.
+    ///
+    /// `2^s × (… × 2^s) × 2^m → A`
+    fn symbols_expression<F: Fn(&MultiTapeTransition) -> Value>(
+        &self,
+        context: &mut Context<Turing>,
+        n_tapes: usize,
+        prefix: usize,
+        f: &F,
+    ) -> Rc<CommitNode<Turing>> {
+        // Let B = 2^s, R = 2^s × (… × 2^s) with `n_tapes - 1` factors and M = 2^m
+        // Let A' = M for the last tape and A' = R × M otherwise
+        let expressions: Vec<Rc<CommitNode<Turing>>> = if n_tapes == 1 {
+            let default = f(&self.transitions[0][0]);
+            // M → A
+            (0..self.n_symbols)
+                .map(|b| {
+                    let r = prefix * self.n_symbols + b;
+                    let leaves = state_leaves(
+                        self.n_states(),
+                        |q| f(&self.transitions[q][r]),
+                        default.clone(),
+                    );
+                    lookup(context, self.state_bits(), &leaves)
+                })
+                .collect()
+        } else {
+            // R × M → A
+            (0..self.n_symbols)
+                .map(|b| {
+                    self.symbols_expression(context, n_tapes - 1, prefix * self.n_symbols + b, f)
+                })
+                .collect()
+        };
+        let branches: Vec<Rc<CommitNode<Turing>>> = (0..1 << self.symbol_bits())
+            .map(|b| expressions.get(b).unwrap_or(&expressions[0]).clone())
+            .collect();
+        // B × A' → A
+        let select_b = select(context, self.symbol_bits(), &branches);
+
+        if n_tapes == 1 {
+            return select_b;
+        }
+
+        // B → B
+        let iden_b = CommitNode::iden(context).unwrap();
+        // B × R → B
+        let take_b_r = CommitNode::take(context, iden_b).unwrap();
+        // (B × R) × M → B
+        let b = CommitNode::take(context, take_b_r).unwrap();
+        // R → R
+        let iden_r = CommitNode::iden(context).unwrap();
+        // B × R → R
+        let drop_b_r = CommitNode::drop(context, iden_r).unwrap();
+        // (B × R) × M → R
+        let rest = CommitNode::take(context, drop_b_r).unwrap();
+        // M → M
+        let iden_m = CommitNode::iden(context).unwrap();
+        // (B × R) × M → M
+        let q = CommitNode::drop(context, iden_m).unwrap();
+        // (B × R) × M → R × M
+        let pair_rest_q = CommitNode::pair(context, rest, q).unwrap();
+        // (B × R) × M → B × (R × M)
+        let pair_b_rest_q = CommitNode::pair(context, b, pair_rest_q).unwrap();
+
+        // (B × R) × M → A
+        CommitNode::comp(context, pair_b_rest_q, select_b).unwrap()
+    }
+}
+
+impl MultiTapeMachine for MultiTapeTableMachine {
+    fn n_tapes(&self) -> usize {
+        self.n_tapes
+    }
+
+    fn left(&self, context: &mut Context<Turing>, tape: usize) -> Rc<CommitNode<Turing>> {
+        self.transition_expression(context, &|t| {
+            Value::u1((t.directions[tape] == Direction::Left) as u8)
+        })
+    }
+
+    fn stay(&self, context: &mut Context<Turing>, tape: usize) -> Option<Rc<CommitNode<Turing>>> {
+        if !self
+            .transitions
+            .iter()
+            .flatten()
+            .any(|t| t.directions[tape] == Direction::Stay)
+        {
+            return None;
+        }
+
+        Some(self.transition_expression(context, &|t| {
+            Value::u1((t.directions[tape] == Direction::Stay) as u8)
+        }))
+    }
+
+    fn state(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        self.transition_expression(context, &|t| self.state_value(t.next))
+    }
+
+    fn write(&self, context: &mut Context<Turing>, tape: usize) -> Rc<CommitNode<Turing>> {
+        self.transition_expression(context, &|t| self.symbol_value(t.write[tape]))
+    }
+
+    fn initial(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        let leaves = state_leaves(
+            self.n_states(),
+            |q| Value::u1(self.initial[q] as u8),
+            Value::u1(0),
+        );
+        lookup(context, self.state_bits(), &leaves)
+    }
+
+    fn accepting(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        let leaves = state_leaves(
+            self.n_states(),
+            |q| Value::u1(self.accepting[q] as u8),
+            Value::u1(0),
+        );
+        lookup(context, self.state_bits(), &leaves)
+    }
+
+    fn rejecting(&self, context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        if !self.rejecting.iter().any(|&rejecting| rejecting) {
+            return None;
+        }
+
+        let leaves = state_leaves(
+            self.n_states(),
+            |q| Value::u1(self.rejecting[q] as u8),
+            Value::u1(0),
+        );
+        Some(lookup(context, self.state_bits(), &leaves))
+    }
+
+    fn eq_state(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        util::eq_bits(context, self.state_bits())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::machine::{TableMachine, Transition};
+    use simplicity::exec::BitMachine;
+
+    /// Machine with two tapes that copies the ones at the head of the first tape onto the second tape
+    fn copy() -> MultiTapeTableMachine {
+        use Direction::*;
+        let t = MultiTapeTransition::new;
+        let transitions = vec![
+            vec![
+                t(vec![0, 0], vec![Stay, Stay], 1),
+                t(vec![0, 1], vec![Stay, Stay], 1),
+                t(vec![1, 1], vec![Right, Right], 0),
+                t(vec![1, 1], vec![Right, Right], 0),
+            ],
+            vec![
+                t(vec![0, 0], vec![Stay, Stay], 1),
+                t(vec![0, 1], vec![Stay, Stay], 1),
+                t(vec![1, 0], vec![Stay, Stay], 1),
+                t(vec![1, 1], vec![Stay, Stay], 1),
+            ],
+        ];
+        MultiTapeTableMachine::new(2, transitions, &[0], &[1])
+    }
+
+    /// Apply the given expression to the given input value
+    fn apply(
+        context: &mut Context<Turing>,
+        expression: Rc<CommitNode<Turing>>,
+        input: &Value,
+    ) -> Value {
+        let scribe_input = CommitNode::scribe(context, input).unwrap();
+        let program = CommitNode::comp(context, scribe_input, expression)
+            .unwrap()
+            .finalize(std::iter::empty())
+            .unwrap();
+
+        let mut mac = BitMachine::for_program(&program);
+        mac.exec(&program, &()).unwrap()
+    }
+
+    #[test]
+    fn transition() {
+        let machine = copy();
+        let mut context = Context::default();
+        let state = machine.state(&mut context);
+
+        for q in 0..machine.n_states() {
+            for read in [[0, 0], [0, 1], [1, 0], [1, 1]] {
+                let transition = machine.transition(q, &read);
+                let input = Value::prod(machine.symbols_value(&read), machine.state_value(q));
+
+                let output = apply(&mut context, state.clone(), &input);
+                assert_eq!(machine.state_value(transition.next), output);
+
+                for tape in 0..machine.n_tapes() {
+                    let left = machine.left(&mut context, tape);
+                    let output = apply(&mut context, left, &input);
+                    let expected = transition.directions[tape] == Direction::Left;
+                    assert_eq!(Value::u1(expected as u8), output);
+
+                    let stay = machine.stay(&mut context, tape).unwrap();
+                    let output = apply(&mut context, stay, &input);
+                    let expected = transition.directions[tape] == Direction::Stay;
+                    assert_eq!(Value::u1(expected as u8), output);
+
+                    let write = machine.write(&mut context, tape);
+                    let output = apply(&mut context, write, &input);
+                    assert_eq!(machine.symbol_value(transition.write[tape]), output);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn single_tape() {
+        let table = "1RB2LB1RZ_2LA2RB1LB".parse::<TableMachine>().unwrap();
+        let transitions = (0..table.n_states())
+            .map(|q| {
+                (0..table.n_symbols() as u8)
+                    .map(|b| {
+                        let Transition {
+                            write,
+                            direction,
+                            next,
+                        } = table.transition(q, b);
+                        MultiTapeTransition::new(vec![write], vec![direction], next)
+                    })
+                    .collect()
+            })
+            .collect();
+        let machine = MultiTapeTableMachine::new(1, transitions, &[0], &[2]);
+
+        let mut context = Context::default();
+        assert_eq!(
+            util::encode_base64(&table.state(&mut context)),
+            util::encode_base64(&MultiTapeMachine::state(&machine, &mut context))
+        );
+        assert_eq!(
+            util::encode_base64(&table.write(&mut context)),
+            util::encode_base64(&MultiTapeMachine::write(&machine, &mut context, 0))
+        );
+    }
+}
@@ -0,0 +1,415 @@
+use crate::jet::application::Turing;
+use crate::machine::table::{
+    check_n_states, check_n_symbols, check_transition, encoding_bits, lookup, select, state_flags,
+    state_leaves,
+};
+use crate::machine::{Direction, DynMachine, Transition};
+use crate::util;
+use simplicity::core::{Context, Value};
+use simplicity::CommitNode;
+use std::rc::Rc;
+
+/// Nondeterministic Turing machine that is given by a transition relation.
+///
+/// The relation assigns to each state `q` and read symbol `b` a nonempty list of [`Transition`]s to choose from.
+/// Each configuration records the choice of transition that is taken in the next step,
+/// so the verifier checks that the choice is allowed, see [`DynMachine::allowed`],
+/// and then verifies the chosen transition as for a deterministic machine.
+///
+/// States are encoded as `2^c × 2^m`, where the first component is the `c`-bit choice
+/// and the second component is the `m`-bit state, as in [`super::TableMachine`].
+/// The successor state leaves the next choice open.
+/// Tape symbols are encoded as `s`-bit strings.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct NondeterministicTableMachine {
+    transitions: Vec<Vec<Vec<Transition>>>,
+    initial: Vec<bool>,
+    accepting: Vec<bool>,
+}
+
+impl NondeterministicTableMachine {
+    /// Create a machine from its transition relation and its initial and accepting states.
+    ///
+    /// The transitions of state `q` upon reading symbol `b` are `transitions[q][b]`.
+    /// Every state must have at least one transition for each of the (at least two) symbols.
+    pub fn new(
+        transitions: Vec<Vec<Vec<Transition>>>,
+        initial: &[usize],
+        accepting: &[usize],
+    ) -> Self {
+        let n_states = transitions.len();
+        check_n_states(n_states);
+
+        let n_symbols = transitions[0].len();
+        check_n_symbols(n_symbols);
+
+        for state_transitions in &transitions {
+            assert_eq!(
+                n_symbols,
+                state_transitions.len(),
+                "Every state must have transitions for each symbol"
+            );
+            for choices in state_transitions {
+                assert!(
+                    !choices.is_empty(),
+                    "Every state must have at least one transition for each symbol"
+                );
+            }
+        }
+
+        for transition in transitions.iter().flatten().flatten() {
+            check_transition(n_states, n_symbols, transition.next, &[transition.write]);
+        }
+
+        let initial_states = state_flags(n_states, initial, "Initial");
+        let accepting_states = state_flags(n_states, accepting, "Accepting");
+
+        Self {
+            transitions,
+            initial: initial_states,
+            accepting: accepting_states,
+        }
+    }
+
+    /// Return the number of states.
+    pub fn n_states(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// Return the number of bits `m` in the state encoding.
+    pub fn state_bits(&self) -> usize {
+        encoding_bits(self.n_states())
+    }
+
+    /// Return the number of tape symbols.
+    pub fn n_symbols(&self) -> usize {
+        self.transitions[0].len()
+    }
+
+    /// Return the number of bits `s` in the symbol encoding.
+    pub fn symbol_bits(&self) -> usize {
+        encoding_bits(self.n_symbols())
+    }
+
+    /// Return the greatest number of transitions to choose from.
+    pub fn n_choices(&self) -> usize {
+        self.transitions
+            .iter()
+            .flatten()
+            .map(Vec::len)
+            .max()
+            .unwrap()
+    }
+
+    /// Return the number of bits `c` in the choice encoding.
+    pub fn choice_bits(&self) -> usize {
+        encoding_bits(self.n_choices())
+    }
+
+    /// Return the transitions of state `q` upon reading symbol `b`.
+    pub fn transitions(&self, q: usize, b: u8) -> &[Transition] {
+        &self.transitions[q][b as usize]
+    }
+
+    /// Check if the given state is an initial state.
+    pub fn is_initial(&self, q: usize) -> bool {
+        self.initial[q]
+    }
+
+    /// Check if the given state is an accepting state.
+    pub fn is_accepting(&self, q: usize) -> bool {
+        self.accepting[q]
+    }
+
+    /// Encode the given state as Simplicity value of type `2^m`.
+    pub fn state_value(&self, q: usize) -> Value {
+        util::bits_value(q, self.state_bits())
+    }
+
+    /// Encode the given choice as Simplicity value of type `2^c`.
+    pub fn choice_value(&self, choice: usize) -> Value {
+        util::bits_value(choice, self.choice_bits())
+    }
+
+    /// Encode the given tape symbol as Simplicity value of type `2^s`.
+    pub fn symbol_value(&self, b: u8) -> Value {
+        util::bits_value(b as usize, self.symbol_bits())
+    }
+
+    /// Add the given choices to the configurations of a computation, as witness for a program commitment.
+    ///
+    /// Each configuration `2^m × (L × K)` becomes `(2^c × 2^m) × (L × K)`,
+    /// where `choices[t]` is the choice that is taken in the step after configuration `t`.
+    /// Configurations without a choice, such as the last one, take choice `0`.
+    pub fn with_choices<'a, I>(
+        &'a self,
+        computation: I,
+        choices: &'a [usize],
+    ) -> impl Iterator<Item = Value> + 'a
+    where
+        I: IntoIterator<Item = Value>,
+        I::IntoIter: 'a,
+    {
+        computation
+            .into_iter()
+            .enumerate()
+            .map(move |(t, configuration)| match configuration {
+                Value::Prod(q, tape_index) => {
+                    let choice = choices.get(t).copied().unwrap_or(0);
+                    Value::prod(Value::prod(self.choice_value(choice), *q), *tape_index)
+                }
+                _ => panic!("Configuration must consist of state, tape and index"),
+            })
+    }
+
+    /// Return an expression that maps the read tape symbol, the choice and the current state
+    /// to the value of `f` for the corresponding transition, or to `fallback` if the choice doesn't exist.
+    ///
+    /// Encodings that don't correspond to any symbol are treated like the symbol `0`.
+    ///
+    /// `2^s × (2^c × 2^m) → A`
+    fn transition_expression<F: Fn(&Transition) -> Value>(
+        &self,
+        context: &mut Context<Turing>,
+        f: F,
+        fallback: Option<Value>,
+    ) -> Rc<CommitNode<Turing>> {
+        let default = f(&self.transitions[0][0][0]);
+        let leaf = |q: usize, b: usize, choice: usize| match self.transitions[q][b].get(choice) {
+            Some(transition) => f(transition),
+            None => fallback
+                .clone()
+                .unwrap_or_else(|| f(&self.transitions[q][b][0])),
+        };
+
+        // 2^c × 2^m → A
+        let choice_expressions: Vec<Rc<CommitNode<Turing>>> = (0..self.n_symbols())
+            .map(|b| {
+                // 2^m → A
+                let lookups: Vec<Rc<CommitNode<Turing>>> = (0..1 << self.choice_bits())
+                    .map(|choice| {
+                        let leaves =
+                            state_leaves(self.n_states(), |q| leaf(q, b, choice), default.clone());
+                        lookup(context, self.state_bits(), &leaves)
+                    })
+                    .collect();
+                select(context, self.choice_bits(), &lookups)
+            })
+            .collect();
+        let branches: Vec<Rc<CommitNode<Turing>>> = (0..1 << self.symbol_bits())
+            .map(|b| {
+                choice_expressions
+                    .get(b)
+                    .unwrap_or(&choice_expressions[0])
+                    .clone()
+            })
+            .collect();
+
+        // 2^s × (2^c × 2^m) → A
+        select(context, self.symbol_bits(), &branches)
+    }
+}
+
+impl DynMachine for NondeterministicTableMachine {
+    /// `left: 2^s × (2^c × 2^m) → 2`
+    fn left(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        self.transition_expression(
+            context,
+            |t| Value::u1((t.direction == Direction::Left) as u8),
+            None,
+        )
+    }
+
+    /// `stay: 2^s × (2^c × 2^m) → 2`
+    fn stay(&self, context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        if !self
+            .transitions
+            .iter()
+            .flatten()
+            .flatten()
+            .any(|t| t.direction == Direction::Stay)
+        {
+            return None;
+        }
+
+        Some(self.transition_expression(
+            context,
+            |t| Value::u1((t.direction == Direction::Stay) as u8),
+            None,
+        ))
+    }
+
+    /// The successor state takes choice `0`, which [`DynMachine::eq_state`] ignores.
+    ///
+    /// `state: 2^s × (2^c × 2^m) → 2^c × 2^m`
+    fn state(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        self.transition_expression(
+            context,
+            |t| Value::prod(self.choice_value(0), self.state_value(t.next)),
+            None,
+        )
+    }
+
+    /// `write: 2^s × (2^c × 2^m) → 2^s`
+    fn write(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        self.transition_expression(context, |t| self.symbol_value(t.write), None)
+    }
+
+    /// `allowed: 2^s × (2^c × 2^m) → 2`
+    fn allowed(&self, context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        Some(self.transition_expression(context, |_| Value::u1(1), Some(Value::u1(0))))
+    }
+
+    /// `initial: 2^c × 2^m → 2`
+    fn initial(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        let leaves = state_leaves(
+            self.n_states(),
+            |q| Value::u1(self.initial[q] as u8),
+            Value::u1(0),
+        );
+        let initial = lookup(context, self.state_bits(), &leaves);
+        CommitNode::drop(context, initial).unwrap()
+    }
+
+    /// `accepting: 2^c × 2^m → 2`
+    fn accepting(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        let leaves = state_leaves(
+            self.n_states(),
+            |q| Value::u1(self.accepting[q] as u8),
+            Value::u1(0),
+        );
+        let accepting = lookup(context, self.state_bits(), &leaves);
+        CommitNode::drop(context, accepting).unwrap()
+    }
+
+    /// Check equality of two states, ignoring their choices.
+    ///
+    /// `eq_state: (2^c × 2^m) × (2^c × 2^m) → 2`
+    fn eq_state(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        // 2^m → 2^m
+        let iden_m = CommitNode::iden(context).unwrap();
+        // 2^c × 2^m → 2^m
+        let drop_c_m = CommitNode::drop(context, iden_m).unwrap();
+        // (2^c × 2^m) × (2^c × 2^m) → 2^m
+        let q = CommitNode::take(context, drop_c_m.clone()).unwrap();
+        // (2^c × 2^m) × (2^c × 2^m) → 2^m
+        let r = CommitNode::drop(context, drop_c_m).unwrap();
+        // (2^c × 2^m) × (2^c × 2^m) → 2^m × 2^m
+        let pair_q_r = CommitNode::pair(context, q, r).unwrap();
+        // 2^m × 2^m → 2
+        let eq_bits = util::eq_bits(context, self.state_bits());
+
+        // (2^c × 2^m) × (2^c × 2^m) → 2
+        CommitNode::comp(context, pair_q_r, eq_bits).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use simplicity::exec::BitMachine;
+
+    /// Machine that writes any string of ones and twos and halts on a blank cell
+    fn guess() -> NondeterministicTableMachine {
+        let transitions = vec![
+            vec![
+                vec![
+                    Transition::new(1, Direction::Right, 0),
+                    Transition::new(2, Direction::Right, 0),
+                    Transition::new(0, Direction::Stay, 1),
+                ],
+                vec![Transition::new(1, Direction::Right, 0)],
+                vec![Transition::new(2, Direction::Right, 0)],
+            ],
+            vec![
+                vec![Transition::new(0, Direction::Stay, 1)],
+                vec![Transition::new(1, Direction::Stay, 1)],
+                vec![Transition::new(2, Direction::Stay, 1)],
+            ],
+        ];
+        NondeterministicTableMachine::new(transitions, &[0], &[1])
+    }
+
+    /// Apply the given expression to the given input value
+    fn apply(
+        context: &mut Context<Turing>,
+        expression: Rc<CommitNode<Turing>>,
+        input: &Value,
+    ) -> Value {
+        let scribe_input = CommitNode::scribe(context, input).unwrap();
+        let program = CommitNode::comp(context, scribe_input, expression)
+            .unwrap()
+            .finalize(std::iter::empty())
+            .unwrap();
+
+        let mut mac = BitMachine::for_program(&program);
+        mac.exec(&program, &()).unwrap()
+    }
+
+    #[test]
+    fn transition() {
+        let machine = guess();
+        assert_eq!(2, machine.choice_bits());
+        let mut context = Context::default();
+        let left = machine.left(&mut context);
+        let stay = machine.stay(&mut context).unwrap();
+        let state = machine.state(&mut context);
+        let write = machine.write(&mut context);
+        let allowed = machine.allowed(&mut context).unwrap();
+
+        for q in 0..machine.n_states() {
+            for b in 0..machine.n_symbols() as u8 {
+                for choice in 0..1 << machine.choice_bits() {
+                    let q_choice =
+                        Value::prod(machine.choice_value(choice), machine.state_value(q));
+                    let input = Value::prod(machine.symbol_value(b), q_choice);
+                    let transitions = machine.transitions(q, b);
+
+                    let output = apply(&mut context, allowed.clone(), &input);
+                    assert_eq!(Value::u1((choice < transitions.len()) as u8), output);
+
+                    let transition = match transitions.get(choice) {
+                        Some(transition) => transition,
+                        None => continue,
+                    };
+
+                    let output = apply(&mut context, left.clone(), &input);
+                    let expected = Value::u1((transition.direction == Direction::Left) as u8);
+                    assert_eq!(expected, output);
+
+                    let output = apply(&mut context, stay.clone(), &input);
+                    let expected = Value::u1((transition.direction == Direction::Stay) as u8);
+                    assert_eq!(expected, output);
+
+                    let output = apply(&mut context, state.clone(), &input);
+                    let expected = Value::prod(
+                        machine.choice_value(0),
+                        machine.state_value(transition.next),
+                    );
+                    assert_eq!(expected, output);
+
+                    let output = apply(&mut context, write.clone(), &input);
+                    assert_eq!(machine.symbol_value(transition.write), output);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn eq_state() {
+        let machine = guess();
+        let mut context = Context::default();
+        let eq_state = machine.eq_state(&mut context);
+
+        for (q, choice_q) in [(0, 0), (0, 2), (1, 1)] {
+            for (r, choice_r) in [(0, 0), (0, 2), (1, 1)] {
+                let input = Value::prod(
+                    Value::prod(machine.choice_value(choice_q), machine.state_value(q)),
+                    Value::prod(machine.choice_value(choice_r), machine.state_value(r)),
+                );
+                let output = apply(&mut context, eq_state.clone(), &input);
+                assert_eq!(Value::u1((q == r) as u8), output);
+            }
+        }
+    }
+}
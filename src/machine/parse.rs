@@ -0,0 +1,332 @@
+use crate::machine::{Direction, TableMachine, Transition};
+use std::str::FromStr;
+
+/// Symbol that denotes the halting state in the standard notation
+const HALT: char = 'Z';
+/// Maximum number of states, named `A` to `Y`
+const MAX_STATES: usize = 25;
+/// Maximum number of symbols, named `0` to `9`
+const MAX_SYMBOLS: usize = 10;
+
+/// Error while parsing a machine in the standard busy-beaver notation.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub enum ParseError {
+    /// The machine has no states
+    Empty,
+    /// The machine has more states than there are letters `A` to `Y`
+    TooManyStates(usize),
+    /// The description of a state does not consist of one transition of three characters per symbol
+    WrongLength {
+        state: char,
+        length: usize,
+        expected: usize,
+    },
+    /// Unknown tape symbol to write
+    InvalidSymbol { state: char, found: char },
+    /// Unknown direction to move the cursor
+    InvalidDirection { state: char, found: char },
+    /// Successor state that does not exist
+    InvalidState { state: char, found: char },
+}
+
+impl std::fmt::Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            ParseError::Empty => write!(f, "Machine has no states"),
+            ParseError::TooManyStates(n_states) => write!(
+                f,
+                "Expected at most {} states but found {}",
+                MAX_STATES, n_states
+            ),
+            ParseError::WrongLength {
+                state,
+                length,
+                expected,
+            } => write!(
+                f,
+                "State {}: Expected {} characters but found {}",
+                state, expected, length
+            ),
+            ParseError::InvalidSymbol { state, found } => write!(
+                f,
+                "State {}: Expected a digit less than the number of symbols but found '{}'",
+                state, found
+            ),
+            ParseError::InvalidDirection { state, found } => write!(
+                f,
+                "State {}: Expected direction 'L', 'R' or 'N' but found '{}'",
+                state, found
+            ),
+            ParseError::InvalidState { state, found } => write!(
+                f,
+                "State {}: Expected successor state or '{}' but found '{}'",
+                state, HALT, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Return the letter of the given state: `A`, `B`, `C`, ...
+fn state_letter(q: usize) -> char {
+    (b'A' + q as u8) as char
+}
+
+/// Parse a machine in the standard busy-beaver notation, such as `1RB1LB_1LA1RZ`.
+///
+/// States are separated by `_` and named `A`, `B`, `C`, ... in order.
+/// Each state lists its transitions upon reading `0`, `1`, ...
+/// as the symbol to write, the direction to move (`L`, `R`, or `N` to stay in place) and the successor state.
+/// The number of symbols follows from the length of the first state, such as `1RB2LB1RZ_2LA2RB1LB`
+/// for a machine with three symbols. The blank symbol is `0`.
+///
+/// The machine starts in state `A`.
+/// Transitions to `Z` go to an additional halting state, which is the only accepting state
+/// and which has no transitions.
+/// Undefined transitions `---` have no transition either, so the machine halts there without accepting;
+/// [`crate::DynVerifier::with_halting`] certifies such halts.
+impl FromStr for TableMachine {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(ParseError::Empty);
+        }
+
+        let descriptions: Vec<&str> = s.split('_').collect();
+        let n_states = descriptions.len();
+        let halt = n_states;
+
+        if n_states > MAX_STATES {
+            return Err(ParseError::TooManyStates(n_states));
+        }
+
+        let parse_state = |state: char, found: char| match found {
+            HALT => Ok(halt),
+            'A'..='Y' if ((found as u8 - b'A') as usize) < n_states => {
+                Ok((found as u8 - b'A') as usize)
+            }
+            _ => Err(ParseError::InvalidState { state, found }),
+        };
+
+        let first_length = descriptions[0].chars().count();
+        let n_symbols = first_length / 3;
+
+        if !first_length.is_multiple_of(3) || !(2..=MAX_SYMBOLS).contains(&n_symbols) {
+            return Err(ParseError::WrongLength {
+                state: state_letter(0),
+                length: first_length,
+                expected: 6,
+            });
+        }
+
+        let mut transitions = Vec::with_capacity(n_states + 1);
+
+        for (q, description) in descriptions.iter().enumerate() {
+            let state = state_letter(q);
+            let chars: Vec<char> = description.chars().collect();
+
+            if chars.len() != 3 * n_symbols {
+                return Err(ParseError::WrongLength {
+                    state,
+                    length: chars.len(),
+                    expected: 3 * n_symbols,
+                });
+            }
+
+            let mut state_transitions = Vec::with_capacity(n_symbols);
+
+            for transition in chars.chunks(3) {
+                if transition == ['-', '-', '-'] {
+                    state_transitions.push(None);
+                    continue;
+                }
+
+                let write = match transition[0].to_digit(10) {
+                    Some(write) if (write as usize) < n_symbols => write as u8,
+                    _ => {
+                        return Err(ParseError::InvalidSymbol {
+                            state,
+                            found: transition[0],
+                        })
+                    }
+                };
+                let direction = match transition[1] {
+                    'L' => Direction::Left,
+                    'R' => Direction::Right,
+                    'N' => Direction::Stay,
+                    found => return Err(ParseError::InvalidDirection { state, found }),
+                };
+                let next = parse_state(state, transition[2])?;
+
+                state_transitions.push(Some(Transition::new(write, direction, next)));
+            }
+
+            transitions.push(state_transitions);
+        }
+
+        transitions.push(vec![None; n_symbols]);
+
+        Ok(TableMachine::partial(transitions, &[0], &[halt]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let machine: TableMachine = "1RB1LB_1LA1RZ".parse().unwrap();
+
+        assert_eq!(3, machine.n_states());
+        assert!(machine.is_initial(0));
+        assert!(machine.is_accepting(2));
+        assert!(!machine.is_accepting(0) && !machine.is_accepting(1));
+
+        let q_b_transition = vec![
+            (0, 0, Transition::new(1, Direction::Right, 1)),
+            (0, 1, Transition::new(1, Direction::Left, 1)),
+            (1, 0, Transition::new(1, Direction::Left, 0)),
+            (1, 1, Transition::new(1, Direction::Right, 2)),
+        ];
+
+        for (q, b, transition) in q_b_transition {
+            assert_eq!(transition, machine.transition(q, b));
+        }
+    }
+
+    #[test]
+    fn parse_champions() {
+        let input_n_states = vec![
+            ("1RB1LB_1LA1RZ", 2),
+            ("1RB1RZ_1LB0RC_1LC1LA", 3),
+            ("1RB1LB_1LA0LC_1RZ1LD_1RD0RA", 4),
+            ("1RB1LC_1RC1RB_1RD0LE_1LA1LD_1RZ0LA", 5),
+        ];
+
+        for (input, n_states) in input_n_states {
+            let machine: TableMachine = input.parse().unwrap();
+            assert_eq!(n_states + 1, machine.n_states());
+        }
+    }
+
+    #[test]
+    fn parse_symbols() {
+        let input_n_states_n_symbols = vec![
+            ("1RB2LB1RZ_2LA2RB1LB", 2, 3),
+            ("1RB2LA1LA_2LA2RB0RC_1RZ1LC1RA", 3, 3),
+            ("1RB3LA1LA1RA_2LA1RZ3RA3RB", 2, 4),
+        ];
+
+        for (input, n_states, n_symbols) in input_n_states_n_symbols {
+            let machine: TableMachine = input.parse().unwrap();
+            assert_eq!(n_states + 1, machine.n_states());
+            assert_eq!(n_symbols, machine.n_symbols());
+        }
+
+        let machine: TableMachine = "1RB2LB1RZ_2LA2RB1LB".parse().unwrap();
+        assert_eq!(
+            Transition::new(2, Direction::Left, 0),
+            machine.transition(1, 0)
+        );
+        assert_eq!(
+            Transition::new(2, Direction::Right, 2),
+            machine.transition(2, 2)
+        );
+    }
+
+    #[test]
+    fn parse_stay() {
+        let machine: TableMachine = "1NB1LB_0RA1NZ".parse().unwrap();
+
+        assert_eq!(
+            Transition::new(1, Direction::Stay, 1),
+            machine.transition(0, 0)
+        );
+        assert_eq!(
+            Transition::new(1, Direction::Stay, 2),
+            machine.transition(1, 1)
+        );
+    }
+
+    #[test]
+    fn parse_undefined() {
+        let machine: TableMachine = "1RB---_1LA0LA".parse().unwrap();
+
+        assert!(machine.is_defined(0, 0));
+        assert!(!machine.is_defined(0, 1));
+        assert!(machine.is_defined(1, 0) && machine.is_defined(1, 1));
+        assert!(!machine.is_defined(2, 0) && !machine.is_defined(2, 1));
+    }
+
+    #[test]
+    fn parse_error() {
+        let input_error = vec![
+            ("", ParseError::Empty),
+            (
+                "1RB1LB_1LA",
+                ParseError::WrongLength {
+                    state: 'B',
+                    length: 3,
+                    expected: 6,
+                },
+            ),
+            (
+                "1RB1LB1RA_1LA1RZ",
+                ParseError::WrongLength {
+                    state: 'B',
+                    length: 6,
+                    expected: 9,
+                },
+            ),
+            (
+                "1RB1L",
+                ParseError::WrongLength {
+                    state: 'A',
+                    length: 5,
+                    expected: 6,
+                },
+            ),
+            (
+                "1RB2LB_1LA1RZ",
+                ParseError::InvalidSymbol {
+                    state: 'A',
+                    found: '2',
+                },
+            ),
+            (
+                "1RB2LB1RZ_3LA2RB1LB",
+                ParseError::InvalidSymbol {
+                    state: 'B',
+                    found: '3',
+                },
+            ),
+            (
+                "1RB1LB_1SA1RZ",
+                ParseError::InvalidDirection {
+                    state: 'B',
+                    found: 'S',
+                },
+            ),
+            (
+                "1RC1LB_1LA1RZ",
+                ParseError::InvalidState {
+                    state: 'A',
+                    found: 'C',
+                },
+            ),
+        ];
+
+        for (input, error) in input_error {
+            assert_eq!(Err(error), input.parse::<TableMachine>());
+        }
+
+        let input = vec!["1RA1LA"; 26].join("_");
+        assert_eq!(
+            Err(ParseError::TooManyStates(26)),
+            input.parse::<TableMachine>()
+        );
+    }
+}
@@ -0,0 +1,776 @@
+use crate::jet::application::Turing;
+use crate::machine::DynMachine;
+use crate::util;
+use simplicity::core::{Context, Value};
+use simplicity::CommitNode;
+use std::rc::Rc;
+
+/// Direction in which the cursor moves after a transition.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub enum Direction {
+    /// Move the cursor one cell to the left
+    Left,
+    /// Move the cursor one cell to the right
+    Right,
+    /// Leave the cursor where it is
+    Stay,
+}
+
+/// Entry of a transition table:
+/// which symbol to write, where to move the cursor and which state to go to.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct Transition {
+    /// Tape symbol to write
+    pub write: u8,
+    /// Direction in which to move the cursor
+    pub direction: Direction,
+    /// Successor state
+    pub next: usize,
+}
+
+impl Transition {
+    /// Create a new transition.
+    pub fn new(write: u8, direction: Direction, next: usize) -> Self {
+        Self {
+            write,
+            direction,
+            next,
+        }
+    }
+}
+
+/// Turing machine that is given by a transition table.
+///
+/// The table assigns to each state `q` and read symbol `b` a [`Transition`].
+/// The Simplicity expressions of the machine are synthesized from the table,
+/// for any number of states and tape symbols.
+///
+/// States are encoded as `m`-bit strings, where `m` is the least number of bits
+/// that can hold every state (at least one). See [`util::bits_value`].
+/// Tape symbols are encoded likewise as `s`-bit strings,
+/// so machines with the binary alphabet read and write symbols of type `2`.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TableMachine {
+    transitions: Vec<Vec<Transition>>,
+    initial: Vec<bool>,
+    accepting: Vec<bool>,
+    rejecting: Vec<bool>,
+    defined: Vec<Vec<bool>>,
+}
+
+impl TableMachine {
+    /// Create a machine from its transition table and its initial and accepting states.
+    ///
+    /// The transition of state `q` upon reading symbol `b` is `transitions[q][b]`.
+    /// Every state must have a transition for each of the (at least two) symbols.
+    pub fn new(transitions: Vec<Vec<Transition>>, initial: &[usize], accepting: &[usize]) -> Self {
+        let n_states = transitions.len();
+        check_n_states(n_states);
+
+        let n_symbols = transitions[0].len();
+        check_n_symbols(n_symbols);
+
+        for state_transitions in &transitions {
+            assert_eq!(
+                n_symbols,
+                state_transitions.len(),
+                "Every state must have a transition for each symbol"
+            );
+        }
+
+        for transition in transitions.iter().flatten() {
+            check_transition(n_states, n_symbols, transition.next, &[transition.write]);
+        }
+
+        let initial_states = state_flags(n_states, initial, "Initial");
+        let accepting_states = state_flags(n_states, accepting, "Accepting");
+
+        Self {
+            defined: vec![vec![true; n_symbols]; n_states],
+            transitions,
+            initial: initial_states,
+            accepting: accepting_states,
+            rejecting: vec![false; n_states],
+        }
+    }
+
+    /// Create a machine from its partial transition table and its initial and accepting states.
+    ///
+    /// The machine halts upon undefined transitions, which are `None`.
+    /// Otherwise, the table is as in [`TableMachine::new`].
+    pub fn partial(
+        transitions: Vec<Vec<Option<Transition>>>,
+        initial: &[usize],
+        accepting: &[usize],
+    ) -> Self {
+        let defined = transitions
+            .iter()
+            .map(|state_transitions| state_transitions.iter().map(Option::is_some).collect())
+            .collect();
+        // Undefined transitions are never taken, so any valid transition will do
+        let total = transitions
+            .into_iter()
+            .enumerate()
+            .map(|(q, state_transitions)| {
+                state_transitions
+                    .into_iter()
+                    .enumerate()
+                    .map(|(b, t)| t.unwrap_or(Transition::new(b as u8, Direction::Right, q)))
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            defined,
+            ..Self::new(total, initial, accepting)
+        }
+    }
+
+    /// Make the given states rejecting.
+    ///
+    /// Rejecting states must not be accepting.
+    pub fn with_rejecting(mut self, rejecting: &[usize]) -> Self {
+        for &q in rejecting {
+            assert!(q < self.n_states(), "Rejecting state {} does not exist", q);
+            assert!(!self.accepting[q], "State {} is already accepting", q);
+            self.rejecting[q] = true;
+        }
+        self
+    }
+
+    /// Return the number of states.
+    pub fn n_states(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// Return the number of bits `m` in the state encoding.
+    pub fn state_bits(&self) -> usize {
+        encoding_bits(self.n_states())
+    }
+
+    /// Return the number of tape symbols.
+    pub fn n_symbols(&self) -> usize {
+        self.transitions[0].len()
+    }
+
+    /// Return the number of bits `s` in the symbol encoding.
+    pub fn symbol_bits(&self) -> usize {
+        encoding_bits(self.n_symbols())
+    }
+
+    /// Return the transition of state `q` upon reading symbol `b`.
+    pub fn transition(&self, q: usize, b: u8) -> Transition {
+        self.transitions[q][b as usize]
+    }
+
+    /// Check if the transition of state `q` upon reading symbol `b` is defined.
+    ///
+    /// The machine halts otherwise.
+    pub fn is_defined(&self, q: usize, b: u8) -> bool {
+        self.defined[q][b as usize]
+    }
+
+    /// Check if the given state is an initial state.
+    pub fn is_initial(&self, q: usize) -> bool {
+        self.initial[q]
+    }
+
+    /// Check if the given state is an accepting state.
+    pub fn is_accepting(&self, q: usize) -> bool {
+        self.accepting[q]
+    }
+
+    /// Check if the given state is a rejecting state.
+    pub fn is_rejecting(&self, q: usize) -> bool {
+        self.rejecting[q]
+    }
+
+    /// Encode the given state as Simplicity value of type `2^m`.
+    pub fn state_value(&self, q: usize) -> Value {
+        util::bits_value(q, self.state_bits())
+    }
+
+    /// Encode the given tape symbol as Simplicity value of type `2^s`.
+    pub fn symbol_value(&self, b: u8) -> Value {
+        util::bits_value(b as usize, self.symbol_bits())
+    }
+
+    /// Go left, given the read tape symbol and current state?
+    ///
+    /// `left: 2^s × 2^m → 2`
+    pub fn left(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        self.transition_expression(context, |t| {
+            Value::u1((t.direction == Direction::Left) as u8)
+        })
+    }
+
+    /// Stay in place, given the read tape symbol and current state?
+    ///
+    /// Return `None` if no transition stays in place.
+    ///
+    /// `stay: 2^s × 2^m → 2`
+    pub fn stay(&self, context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        if !self
+            .transitions
+            .iter()
+            .flatten()
+            .any(|t| t.direction == Direction::Stay)
+        {
+            return None;
+        }
+
+        Some(self.transition_expression(context, |t| {
+            Value::u1((t.direction == Direction::Stay) as u8)
+        }))
+    }
+
+    /// Successor state, given the read tape symbol and current state.
+    ///
+    /// `state: 2^s × 2^m → 2^m`
+    pub fn state(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        self.transition_expression(context, |t| self.state_value(t.next))
+    }
+
+    /// Tape symbol to write, given the read tape symbol and current state.
+    ///
+    /// `write: 2^s × 2^m → 2^s`
+    pub fn write(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        self.transition_expression(context, |t| self.symbol_value(t.write))
+    }
+
+    /// Check if the transition is undefined, given the read tape symbol and current state.
+    ///
+    /// Return `None` if all transitions are defined.
+    ///
+    /// `halts: 2^s × 2^m → 2`
+    pub fn halts(&self, context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        if self.defined.iter().flatten().all(|&defined| defined) {
+            return None;
+        }
+
+        Some(self.table_expression(context, |q, b| Value::u1(!self.defined[q][b] as u8)))
+    }
+
+    /// Check if the given state is an initial state.
+    ///
+    /// `initial: 2^m → 2`
+    pub fn initial(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        let leaves = state_leaves(
+            self.n_states(),
+            |q| Value::u1(self.initial[q] as u8),
+            Value::u1(0),
+        );
+        lookup(context, self.state_bits(), &leaves)
+    }
+
+    /// Check if the given state is an accepting state.
+    ///
+    /// `accepting: 2^m → 2`
+    pub fn accepting(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        let leaves = state_leaves(
+            self.n_states(),
+            |q| Value::u1(self.accepting[q] as u8),
+            Value::u1(0),
+        );
+        lookup(context, self.state_bits(), &leaves)
+    }
+
+    /// Check if the given state is a rejecting state.
+    ///
+    /// Return `None` if there are no rejecting states.
+    ///
+    /// `rejecting: 2^m → 2`
+    pub fn rejecting(&self, context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        if !self.rejecting.iter().any(|&rejecting| rejecting) {
+            return None;
+        }
+
+        let leaves = state_leaves(
+            self.n_states(),
+            |q| Value::u1(self.rejecting[q] as u8),
+            Value::u1(0),
+        );
+        Some(lookup(context, self.state_bits(), &leaves))
+    }
+
+    /// Check equality of two states
+    ///
+    /// `eq_state: 2^m × 2^m → 2`
+    pub fn eq_state(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        util::eq_bits(context, self.state_bits())
+    }
+
+    /// Return an expression that maps the read tape symbol and current state
+    /// to the value of `f` for the corresponding transition.
+    ///
+    /// Encodings that don't correspond to any symbol are treated like the symbol `0`.
+    ///
+    /// `2^s × 2^m → A`
+    fn transition_expression<F: Fn(&Transition) -> Value>(
+        &self,
+        context: &mut Context<Turing>,
+        f: F,
+    ) -> Rc<CommitNode<Turing>> {
+        self.table_expression(context, |q, b| f(&self.transitions[q][b]))
+    }
+
+    /// Return an expression that maps the read tape symbol `b` and current state `q`
+    /// to the value of `f(q, b)`.
+    ///
+    /// Encodings that don't correspond to any symbol are treated like the symbol `0`.
+    ///
+    /// `2^s × 2^m → A`
+    fn table_expression<F: Fn(usize, usize) -> Value>(
+        &self,
+        context: &mut Context<Turing>,
+        f: F,
+    ) -> Rc<CommitNode<Turing>> {
+        let default = f(0, 0);
+        // 2^m → A
+        let lookups: Vec<Rc<CommitNode<Turing>>> = (0..self.n_symbols())
+            .map(|b| {
+                let leaves = state_leaves(self.n_states(), |q| f(q, b), default.clone());
+                lookup(context, self.state_bits(), &leaves)
+            })
+            .collect();
+        let branches: Vec<Rc<CommitNode<Turing>>> = (0..1 << self.symbol_bits())
+            .map(|b| lookups.get(b).unwrap_or(&lookups[0]).clone())
+            .collect();
+
+        // 2^s × 2^m → A
+        select(context, self.symbol_bits(), &branches)
+    }
+}
+
+impl DynMachine for TableMachine {
+    fn left(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        TableMachine::left(self, context)
+    }
+
+    fn stay(&self, context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        TableMachine::stay(self, context)
+    }
+
+    fn state(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        TableMachine::state(self, context)
+    }
+
+    fn write(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        TableMachine::write(self, context)
+    }
+
+    fn halts(&self, context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        TableMachine::halts(self, context)
+    }
+
+    fn initial(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        TableMachine::initial(self, context)
+    }
+
+    fn accepting(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        TableMachine::accepting(self, context)
+    }
+
+    fn rejecting(&self, context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        TableMachine::rejecting(self, context)
+    }
+
+    fn eq_state(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        TableMachine::eq_state(self, context)
+    }
+}
+
+/// Return the least number of bits that can hold `n` values (at least one).
+pub(super) fn encoding_bits(n: usize) -> usize {
+    let mut bits = 1;
+    while (1 << bits) < n {
+        bits += 1;
+    }
+    bits
+}
+
+/// Check that there are between 1 and 256 states (8-bit encoding).
+pub(super) fn check_n_states(n_states: usize) {
+    assert!(n_states > 0, "There must be at least one state");
+    assert!(
+        n_states <= 256,
+        "There must be at most 256 states (8-bit encoding)"
+    );
+}
+
+/// Check that there are between 2 and 256 symbols (8-bit encoding).
+pub(super) fn check_n_symbols(n_symbols: usize) {
+    assert!(n_symbols >= 2, "There must be at least two symbols");
+    assert!(
+        n_symbols <= 256,
+        "There must be at most 256 symbols (8-bit encoding)"
+    );
+}
+
+/// Check that the successor state `next` and the symbols to `write` exist.
+pub(super) fn check_transition(n_states: usize, n_symbols: usize, next: usize, write: &[u8]) {
+    assert!(next < n_states, "Successor state {} does not exist", next);
+    for &write in write {
+        assert!(
+            (write as usize) < n_symbols,
+            "Symbol {} does not exist",
+            write
+        );
+    }
+}
+
+/// Return for each of the `n_states` states whether it is one of the given `states`.
+///
+/// `kind` names the states in the error message, e.g., `"Initial"`.
+pub(super) fn state_flags(n_states: usize, states: &[usize], kind: &str) -> Vec<bool> {
+    let mut flags = vec![false; n_states];
+    for &q in states {
+        assert!(q < n_states, "{} state {} does not exist", kind, q);
+        flags[q] = true;
+    }
+    flags
+}
+
+/// Return the values of `f` for all `2^m` encodings of `n_states` states.
+/// Encodings that don't correspond to any state are mapped to `default`.
+pub(super) fn state_leaves<F: Fn(usize) -> Value>(
+    n_states: usize,
+    f: F,
+    default: Value,
+) -> Vec<Value> {
+    (0..1 << encoding_bits(n_states))
+        .map(|q| if q < n_states { f(q) } else { default.clone() })
+        .collect()
+}
+
+/// Return an expression that runs `branches[x]` on `a`, given the `n`-bit string `x`.
+///
+/// `select: 2^n × A → B` where `branches[x]: A → B`
+pub(super) fn select(
+    context: &mut Context<Turing>,
+    n: usize,
+    branches: &[Rc<CommitNode<Turing>>],
+) -> Rc<CommitNode<Turing>> {
+    debug_assert_eq!(1 << n, branches.len());
+    let (branches_0, branches_1) = branches.split_at(branches.len() / 2);
+
+    if n == 1 {
+        // 2 × A → B
+        return CommitNode::cond(context, branches_1[0].clone(), branches_0[0].clone()).unwrap();
+    }
+
+    // Let R = 2^(n - 1)
+    // 2 → 2
+    let iden_2 = CommitNode::iden(context).unwrap();
+    // 2 × R → 2
+    let take_2_r = CommitNode::take(context, iden_2).unwrap();
+    // (2 × R) × A → 2
+    let msb = CommitNode::take(context, take_2_r).unwrap();
+    // R → R
+    let iden_r = CommitNode::iden(context).unwrap();
+    // 2 × R → R
+    let drop_2_r = CommitNode::drop(context, iden_r).unwrap();
+    // (2 × R) × A → R
+    let rest = CommitNode::take(context, drop_2_r).unwrap();
+    // A → A
+    let iden_a = CommitNode::iden(context).unwrap();
+    // (2 × R) × A → A
+    let a = CommitNode::drop(context, iden_a).unwrap();
+    // (2 × R) × A → R × A
+    let pair_rest_a = CommitNode::pair(context, rest, a).unwrap();
+    // (2 × R) × A → 2 × (R × A)
+    let pair_msb_rest_a = CommitNode::pair(context, msb, pair_rest_a).unwrap();
+
+    // R × A → B
+    let select_1 = select(context, n - 1, branches_1);
+    // R × A → B
+    let select_0 = select(context, n - 1, branches_0);
+    // 2 × (R × A) → B
+    let cond_1_0 = CommitNode::cond(context, select_1, select_0).unwrap();
+
+    // (2 × R) × A → B
+    CommitNode::comp(context, pair_msb_rest_a, cond_1_0).unwrap()
+}
+
+/// Return an expression that maps the `n`-bit string `x` to `leaves[x]`.
+///
+/// `lookup: 2^n → A`
+pub(super) fn lookup(
+    context: &mut Context<Turing>,
+    n: usize,
+    leaves: &[Value],
+) -> Rc<CommitNode<Turing>> {
+    debug_assert_eq!(1 << n, leaves.len());
+
+    if leaves.iter().all(|leaf| leaf == &leaves[0]) {
+        // 2^n → A
+        return CommitNode::scribe(context, &leaves[0]).unwrap();
+    }
+
+    let (leaves_0, leaves_1) = leaves.split_at(leaves.len() / 2);
+
+    if n == 1 {
+        // 2 → 2
+        let iden_2 = CommitNode::iden(context).unwrap();
+        // 2 → 1
+        let unit_2 = CommitNode::unit(context).unwrap();
+        // 2 → 2 × 1
+        let adaptor = CommitNode::pair(context, iden_2, unit_2).unwrap();
+
+        // 1 → A
+        let scribe_1 = CommitNode::scribe(context, &leaves_1[0]).unwrap();
+        // 1 → A
+        let scribe_0 = CommitNode::scribe(context, &leaves_0[0]).unwrap();
+        // 2 × 1 → A
+        let cond_1_0 = CommitNode::cond(context, scribe_1, scribe_0).unwrap();
+        // 2 → A
+        return CommitNode::comp(context, adaptor, cond_1_0).unwrap();
+    }
+
+    // 2^(n - 1) → A
+    let lookup_1 = lookup(context, n - 1, leaves_1);
+    // 2^(n - 1) → A
+    let lookup_0 = lookup(context, n - 1, leaves_0);
+    // 2 × 2^(n - 1) → A
+    CommitNode::cond(context, lookup_1, lookup_0).unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use simplicity::exec::BitMachine;
+
+    /// Busy beaver with three states (`A`, `B`, `C`) and a halting state (`H`)
+    fn three_beavers() -> TableMachine {
+        let transitions = vec![
+            vec![
+                Transition::new(1, Direction::Right, 1),
+                Transition::new(1, Direction::Right, 3),
+            ],
+            vec![
+                Transition::new(0, Direction::Right, 2),
+                Transition::new(1, Direction::Right, 1),
+            ],
+            vec![
+                Transition::new(1, Direction::Left, 2),
+                Transition::new(1, Direction::Left, 0),
+            ],
+            vec![
+                Transition::new(0, Direction::Right, 3),
+                Transition::new(1, Direction::Right, 3),
+            ],
+        ];
+        TableMachine::new(transitions, &[0], &[3])
+    }
+
+    /// Machine with five states that cycles through its states and flips the read symbol
+    fn five_cycle() -> TableMachine {
+        let transitions = (0..5)
+            .map(|q| {
+                vec![
+                    Transition::new(1, Direction::Right, (q + 1) % 5),
+                    Transition::new(0, Direction::Left, (q + 1) % 5),
+                ]
+            })
+            .collect();
+        TableMachine::new(transitions, &[0], &[4])
+    }
+
+    /// Busy beaver with two states (`A`, `B`), a halting state (`H`) and three symbols
+    fn two_states_three_symbols() -> TableMachine {
+        let transitions = vec![
+            vec![
+                Transition::new(1, Direction::Right, 1),
+                Transition::new(2, Direction::Left, 1),
+                Transition::new(1, Direction::Right, 2),
+            ],
+            vec![
+                Transition::new(2, Direction::Left, 0),
+                Transition::new(2, Direction::Right, 1),
+                Transition::new(1, Direction::Left, 1),
+            ],
+            vec![
+                Transition::new(0, Direction::Right, 2),
+                Transition::new(1, Direction::Right, 2),
+                Transition::new(2, Direction::Right, 2),
+            ],
+        ];
+        TableMachine::new(transitions, &[0], &[2])
+    }
+
+    /// Machine with three states that moves in all directions
+    fn stay_cycle() -> TableMachine {
+        let transitions = vec![
+            vec![
+                Transition::new(1, Direction::Stay, 1),
+                Transition::new(0, Direction::Right, 2),
+            ],
+            vec![
+                Transition::new(0, Direction::Left, 2),
+                Transition::new(1, Direction::Stay, 0),
+            ],
+            vec![
+                Transition::new(1, Direction::Right, 0),
+                Transition::new(1, Direction::Stay, 2),
+            ],
+        ];
+        TableMachine::new(transitions, &[0], &[2])
+    }
+
+    #[test]
+    fn stay() {
+        let mut context = Context::default();
+        assert!(three_beavers().stay(&mut context).is_none());
+        assert!(stay_cycle().stay(&mut context).is_some());
+    }
+
+    #[test]
+    fn state_bits() {
+        assert_eq!(2, three_beavers().state_bits());
+        assert_eq!(3, five_cycle().state_bits());
+    }
+
+    #[test]
+    fn symbol_bits() {
+        assert_eq!(1, three_beavers().symbol_bits());
+        assert_eq!(2, two_states_three_symbols().symbol_bits());
+    }
+
+    /// Apply the given expression to the given input value
+    fn apply(
+        context: &mut Context<Turing>,
+        expression: Rc<CommitNode<Turing>>,
+        input: &Value,
+    ) -> Value {
+        let scribe_input = CommitNode::scribe(context, input).unwrap();
+        let program = CommitNode::comp(context, scribe_input, expression)
+            .unwrap()
+            .finalize(std::iter::empty())
+            .unwrap();
+
+        let mut mac = BitMachine::for_program(&program);
+        mac.exec(&program, &()).unwrap()
+    }
+
+    #[test]
+    fn transition() {
+        for machine in [
+            three_beavers(),
+            five_cycle(),
+            two_states_three_symbols(),
+            stay_cycle(),
+        ] {
+            let mut context = Context::default();
+            let left = machine.left(&mut context);
+            let stay = machine.stay(&mut context);
+            let state = machine.state(&mut context);
+            let write = machine.write(&mut context);
+
+            for q in 0..machine.n_states() {
+                for b in 0..machine.n_symbols() as u8 {
+                    let transition = machine.transition(q, b);
+                    let input = Value::prod(machine.symbol_value(b), machine.state_value(q));
+
+                    let output = apply(&mut context, left.clone(), &input);
+                    let expected = Value::u1((transition.direction == Direction::Left) as u8);
+                    assert_eq!(expected, output);
+
+                    if let Some(stay) = stay.clone() {
+                        let output = apply(&mut context, stay, &input);
+                        let expected = Value::u1((transition.direction == Direction::Stay) as u8);
+                        assert_eq!(expected, output);
+                    }
+
+                    let output = apply(&mut context, state.clone(), &input);
+                    assert_eq!(machine.state_value(transition.next), output);
+
+                    let output = apply(&mut context, write.clone(), &input);
+                    assert_eq!(machine.symbol_value(transition.write), output);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn initial_accepting() {
+        for machine in [three_beavers(), five_cycle()] {
+            let mut context = Context::default();
+            let initial = machine.initial(&mut context);
+            let accepting = machine.accepting(&mut context);
+
+            for q in 0..machine.n_states() {
+                let input = machine.state_value(q);
+
+                let output = apply(&mut context, initial.clone(), &input);
+                assert_eq!(Value::u1(machine.is_initial(q) as u8), output);
+
+                let output = apply(&mut context, accepting.clone(), &input);
+                assert_eq!(Value::u1(machine.is_accepting(q) as u8), output);
+            }
+        }
+    }
+
+    #[test]
+    fn halts() {
+        let mut context = Context::default();
+        assert!(three_beavers().halts(&mut context).is_none());
+
+        let transitions = vec![
+            vec![
+                Some(Transition::new(1, Direction::Right, 1)),
+                Some(Transition::new(2, Direction::Left, 1)),
+                None,
+            ],
+            vec![
+                Some(Transition::new(2, Direction::Left, 0)),
+                None,
+                Some(Transition::new(1, Direction::Left, 1)),
+            ],
+        ];
+        let machine = TableMachine::partial(transitions, &[0], &[1]);
+        let halts = machine.halts(&mut context).unwrap();
+
+        for q in 0..machine.n_states() {
+            for b in 0..machine.n_symbols() as u8 {
+                let input = Value::prod(machine.symbol_value(b), machine.state_value(q));
+                let output = apply(&mut context, halts.clone(), &input);
+                assert_eq!(Value::u1(!machine.is_defined(q, b) as u8), output);
+                assert_eq!(
+                    (q, b) != (0, 2) && (q, b) != (1, 1),
+                    machine.is_defined(q, b)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rejecting() {
+        let mut context = Context::default();
+        assert!(five_cycle().rejecting(&mut context).is_none());
+
+        let machine = five_cycle().with_rejecting(&[1, 3]);
+        let rejecting = machine.rejecting(&mut context).unwrap();
+
+        for q in 0..machine.n_states() {
+            let input = machine.state_value(q);
+            let output = apply(&mut context, rejecting.clone(), &input);
+            assert_eq!(Value::u1(machine.is_rejecting(q) as u8), output);
+            assert_eq!(q == 1 || q == 3, machine.is_rejecting(q));
+        }
+    }
+
+    #[test]
+    fn eq_state() {
+        let machine = five_cycle();
+        let mut context = Context::default();
+        let eq_state = machine.eq_state(&mut context);
+
+        for q in 0..machine.n_states() {
+            for r in 0..machine.n_states() {
+                let input = Value::prod(machine.state_value(q), machine.state_value(r));
+                let output = apply(&mut context, eq_state.clone(), &input);
+                assert_eq!(Value::u1((q == r) as u8), output);
+            }
+        }
+    }
+}
@@ -0,0 +1,1410 @@
+use crate::computation::MerkleTape;
+use crate::machine::{
+    Direction, MultiTapeTableMachine, NondeterministicTableMachine, TableMachine,
+};
+use crate::util;
+pub use crate::util::one_hot;
+use crate::TranslatedCycle;
+use simplicity::core::Value;
+
+/// Error during the simulation of a Turing machine.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub enum SimulationError {
+    /// The cursor moved off the left edge of the tape in the given step
+    HeadOffLeftEdge { step: usize },
+    /// The cursor moved off the right edge of the tape in the given step
+    HeadOffRightEdge { step: usize },
+    /// No accepting state was reached within the given number of steps
+    NotAccepted { steps: usize },
+    /// Neither an accepting nor a rejecting state was reached within the given number of steps
+    NotDecided { steps: usize },
+    /// The transition in the given step is undefined, so the machine halted before
+    Halted { step: usize },
+    /// The machine didn't halt within the given number of steps
+    NotHalted { steps: usize },
+    /// No configuration repeated within the given number of steps
+    NoCycle { steps: usize },
+    /// The transition relation offers no transition with the given choice in the given step
+    InvalidChoice { step: usize, choice: usize },
+}
+
+impl std::fmt::Display for SimulationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match *self {
+            SimulationError::HeadOffLeftEdge { step } => {
+                write!(
+                    f,
+                    "Step {}: Cursor moved off the left edge of the tape",
+                    step
+                )
+            }
+            SimulationError::HeadOffRightEdge { step } => {
+                write!(
+                    f,
+                    "Step {}: Cursor moved off the right edge of the tape",
+                    step
+                )
+            }
+            SimulationError::NotAccepted { steps } => {
+                write!(f, "No accepting state reached after {} steps", steps)
+            }
+            SimulationError::NotDecided { steps } => {
+                write!(
+                    f,
+                    "No accepting or rejecting state reached after {} steps",
+                    steps
+                )
+            }
+            SimulationError::Halted { step } => {
+                write!(f, "Step {}: The machine halted", step)
+            }
+            SimulationError::NotHalted { steps } => {
+                write!(f, "The machine didn't halt after {} steps", steps)
+            }
+            SimulationError::NoCycle { steps } => {
+                write!(f, "No configuration repeated after {} steps", steps)
+            }
+            SimulationError::InvalidChoice { step, choice } => {
+                write!(
+                    f,
+                    "Step {}: There is no transition with choice {}",
+                    step, choice
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for SimulationError {}
+
+/// Sequence of configurations of a Turing machine,
+/// in the shape that [`util::computation256_of`] expects.
+///
+/// Tapes are `8 · BYTES`-bit strings and indices are `8 · BYTES`-bit one-hot strings.
+/// By default, there are 256 cells, as in [`crate::computation::Computation256`].
+/// The first cell is the most significant bit of the first byte.
+///
+/// Tapes of machines with more than two symbols consist of bit planes, as in
+/// [`crate::computation::Computation256Symbols`]: `tapes` holds the least significant bit of each symbol
+/// and the higher bits are kept internally. See [`Trace::symbol`] and [`Trace::tape_planes`].
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct Trace<const BYTES: usize = 32> {
+    /// States
+    pub states: Vec<u8>,
+    /// Tapes (least significant bit of each symbol)
+    pub tapes: Vec<[u8; BYTES]>,
+    /// One-hot indices
+    pub indices: Vec<[u8; BYTES]>,
+    /// Higher bit planes of the tapes, starting with the plane of the most significant bit
+    ///
+    /// Empty for machines with two symbols.
+    planes: Vec<Vec<[u8; BYTES]>>,
+}
+
+impl<const BYTES: usize> Trace<BYTES> {
+    /// Create a trace of a machine with two symbols.
+    pub fn new(states: Vec<u8>, tapes: Vec<[u8; BYTES]>, indices: Vec<[u8; BYTES]>) -> Self {
+        Self {
+            states,
+            tapes,
+            indices,
+            planes: Vec::new(),
+        }
+    }
+
+    /// Create the trace of a single configuration, where the higher bits of each symbol are blank.
+    fn start(state: usize, tape: [u8; BYTES], head: usize, symbol_bits: usize) -> Self {
+        let planes = match symbol_bits {
+            1 => Vec::new(),
+            _ => vec![vec![[0; BYTES]; symbol_bits - 1]],
+        };
+
+        Self {
+            states: vec![state as u8],
+            tapes: vec![tape],
+            indices: vec![one_hot(head)],
+            planes,
+        }
+    }
+
+    /// Return the number of configurations.
+    pub fn len(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Check if there are no configurations.
+    pub fn is_empty(&self) -> bool {
+        self.states.is_empty()
+    }
+
+    /// Return the trace padded to `len` configurations by repeating its last configuration.
+    ///
+    /// These are the stutter steps of [`crate::DynVerifier::with_stutter`].
+    pub fn stutter(&self, len: usize) -> Self {
+        let mut trace = self.clone();
+        if let (Some(&state), Some(&tape), Some(&index)) =
+            (self.states.last(), self.tapes.last(), self.indices.last())
+        {
+            trace.states.resize(len.max(self.len()), state);
+            trace.tapes.resize(len.max(self.len()), tape);
+            trace.indices.resize(len.max(self.len()), index);
+
+            if let Some(planes) = self.planes.last() {
+                trace.planes.resize(len.max(self.len()), planes.clone());
+            }
+        }
+        trace
+    }
+
+    /// Return the symbol at the given cell of tape `t`.
+    pub fn symbol(&self, t: usize, cell: usize) -> u8 {
+        let planes = self.planes.get(t).map(Vec::as_slice).unwrap_or_default();
+        planes
+            .iter()
+            .chain(std::iter::once(&self.tapes[t]))
+            .fold(0, |symbol, plane| symbol << 1 | get(plane, cell) as u8)
+    }
+
+    /// Check if configurations `t` and `u` are equal.
+    pub fn eq_configuration(&self, t: usize, u: usize) -> bool {
+        self.states[t] == self.states[u]
+            && self.tapes[t] == self.tapes[u]
+            && self.indices[t] == self.indices[u]
+            && self.planes.get(t) == self.planes.get(u)
+    }
+
+    /// Return the certificate of a translated cycle from configuration `start` to configuration `end`,
+    /// if the configuration at `end` repeats the one at `start` shifted to the right by a nonzero number of cells.
+    ///
+    /// The bound of the cycle is the leftmost cell that the head reaches.
+    /// Shifts to the left are never certified, see [`TranslatedCycle`].
+    pub fn translated_cycle(&self, start: usize, end: usize) -> Option<TranslatedCycle> {
+        let head = |t: usize| cell(&self.indices[t]);
+        if head(end) <= head(start) || self.states[start] != self.states[end] {
+            return None;
+        }
+
+        let shift = head(end) - head(start);
+        let bound = (start..end).map(head).min().unwrap();
+        let n_cells = 8 * BYTES;
+        let symbol = |t: usize, cell: usize| match cell {
+            cell if cell < n_cells => self.symbol(t, cell),
+            _ => 0,
+        };
+
+        (bound..n_cells)
+            .all(|cell| symbol(start, cell) == symbol(end, cell + shift))
+            .then_some(TranslatedCycle {
+                start,
+                end,
+                shift,
+                bound,
+            })
+    }
+
+    /// Return the bit planes of tape `t`, starting with the plane of the most significant bit.
+    ///
+    /// This is the format of [`crate::computation::Computation256Symbols`].
+    pub fn tape_planes(&self, t: usize) -> Vec<u8> {
+        let planes = self.planes.get(t).map(Vec::as_slice).unwrap_or_default();
+        planes
+            .iter()
+            .chain(std::iter::once(&self.tapes[t]))
+            .flatten()
+            .copied()
+            .collect()
+    }
+
+    /// Append the configuration that follows the last one,
+    /// after writing `write` at the `head` cell and moving the head to the `next_head` cell.
+    fn push(&mut self, next: usize, write: u8, head: usize, next_head: usize) {
+        let mut tape = *self.tapes.last().unwrap();
+        set(&mut tape, head, write & 1 != 0);
+
+        if let Some(mut planes) = self.planes.last().cloned() {
+            let high_bit = planes.len();
+            for (j, plane) in planes.iter_mut().enumerate() {
+                set(plane, head, write >> (high_bit - j) & 1 != 0);
+            }
+            self.planes.push(planes);
+        }
+
+        self.states.push(next as u8);
+        self.tapes.push(tape);
+        self.indices.push(one_hot(next_head));
+    }
+
+    /// Create a computation from the trace, as witness for a program commitment.
+    ///
+    /// Tapes and indices are balanced trees of 256-bit words,
+    /// as in [`crate::computation::ComputationWide`].
+    /// See [`util::words_value`].
+    pub fn computation<'a, F>(&'a self, state_to_value: F) -> impl Iterator<Item = Value> + 'a
+    where
+        F: (Fn(u8) -> Value) + 'a,
+    {
+        let words_value = |bytes: &[u8; BYTES]| {
+            util::words_value(bytes.chunks(32).map(|word| word.try_into().unwrap()))
+        };
+
+        self.states
+            .iter()
+            .zip(self.tapes.iter().zip(self.indices.iter()))
+            .map(move |(state, (tape, index))| {
+                Value::prod(
+                    state_to_value(*state),
+                    Value::prod(words_value(tape), words_value(index)),
+                )
+            })
+    }
+
+    /// Create a computation from the trace with Merkleized tapes and binary indices,
+    /// as witness for a program commitment.
+    ///
+    /// Each tape is opened at the current cell, see [`MerkleTape::value`].
+    /// The Merkle tree is updated cell by cell along the computation:
+    /// only the cell under the previous index is read from each tape.
+    pub fn computation_merkle<F>(&self, state_to_value: F) -> impl Iterator<Item = Value>
+    where
+        F: Fn(u8) -> Value,
+    {
+        let mut configurations = Vec::with_capacity(self.len());
+        let mut tree: Option<MerkleTape> = None;
+        let mut previous_cell = 0;
+
+        for (state, (tape, index)) in self
+            .states
+            .iter()
+            .zip(self.tapes.iter().zip(self.indices.iter()))
+        {
+            let tree = match &mut tree {
+                None => tree.insert(MerkleTape::new(tape)),
+                Some(tree) => {
+                    tree.set(previous_cell, get(tape, previous_cell));
+                    tree
+                }
+            };
+            let current_cell = cell(index);
+
+            configurations.push(Value::prod(
+                state_to_value(*state),
+                Value::prod(
+                    tree.value(current_cell),
+                    util::bits_value(current_cell, tree.depth()),
+                ),
+            ));
+            previous_cell = current_cell;
+        }
+
+        configurations.into_iter()
+    }
+}
+
+impl Trace {
+    /// Create a computation from the trace, as witness for a program commitment.
+    ///
+    /// See [`util::computation256_of`].
+    pub fn computation256<'a, F>(&'a self, state_to_value: F) -> impl Iterator<Item = Value> + 'a
+    where
+        F: (Fn(u8) -> Value) + 'a,
+    {
+        util::computation256_of(
+            self.states.iter(),
+            self.tapes.iter(),
+            self.indices.iter(),
+            state_to_value,
+        )
+    }
+
+    /// Create a computation from the trace with multi-bit symbols, as witness for a program commitment.
+    ///
+    /// Tapes are bit planes, as in [`crate::computation::Computation256Symbols`].
+    pub fn computation256_symbols<'a, F>(
+        &'a self,
+        state_to_value: F,
+    ) -> impl Iterator<Item = Value> + 'a
+    where
+        F: (Fn(u8) -> Value) + 'a,
+    {
+        (0..self.len()).map(move |t| {
+            let planes = self.tape_planes(t);
+            Value::prod(
+                state_to_value(self.states[t]),
+                Value::prod(
+                    util::planes_value(planes.chunks(32).map(|plane| plane.try_into().unwrap())),
+                    Value::u256_from_slice(&self.indices[t]),
+                ),
+            )
+        })
+    }
+
+    /// Create a computation from the trace with binary indices, as witness for a program commitment.
+    ///
+    /// See [`util::computation256_binary_of`].
+    pub fn computation256_binary<'a, F>(
+        &'a self,
+        state_to_value: F,
+    ) -> impl Iterator<Item = Value> + 'a
+    where
+        F: (Fn(u8) -> Value) + 'a,
+    {
+        util::computation256_binary_of(
+            self.states.iter(),
+            self.tapes.iter(),
+            self.indices.iter().map(|index| cell(index) as u8),
+            state_to_value,
+        )
+    }
+}
+
+/// Native simulator of a [`TableMachine`] that records the trace of its configurations.
+///
+/// The tape consists of `8 · BYTES` cells.
+pub struct Simulator<'a, const BYTES: usize = 32> {
+    machine: &'a TableMachine,
+    trace: Trace<BYTES>,
+    head: usize,
+}
+
+impl<'a, const BYTES: usize> Simulator<'a, BYTES> {
+    /// Create a simulator that starts in the initial state, on the given tape and at the given cell.
+    ///
+    /// If there are multiple initial states, then the least one is chosen.
+    /// The tape holds the least significant bit of each symbol; the higher bits start blank.
+    pub fn new(machine: &'a TableMachine, tape: [u8; BYTES], head: usize) -> Self {
+        assert!(head < 8 * BYTES, "Cell {} is not on the tape", head);
+        let state = (0..machine.n_states())
+            .find(|&q| machine.is_initial(q))
+            .expect("There must be an initial state");
+        let trace = Trace::start(state, tape, head, machine.symbol_bits());
+
+        Self {
+            machine,
+            trace,
+            head,
+        }
+    }
+
+    /// Return the current state.
+    pub fn state(&self) -> usize {
+        *self.trace.states.last().unwrap() as usize
+    }
+
+    /// Return the current cell.
+    pub fn head(&self) -> usize {
+        self.head
+    }
+
+    /// Return the trace of configurations so far.
+    pub fn trace(&self) -> &Trace<BYTES> {
+        &self.trace
+    }
+
+    /// Consume the simulator and return the trace of configurations.
+    pub fn into_trace(self) -> Trace<BYTES> {
+        self.trace
+    }
+
+    /// Execute one transition.
+    pub fn step(&mut self) -> Result<(), SimulationError> {
+        let step = self.trace.len() - 1;
+        let b = self.trace.symbol(step, self.head);
+        if !self.machine.is_defined(self.state(), b) {
+            return Err(SimulationError::Halted { step });
+        }
+        let transition = self.machine.transition(self.state(), b);
+        let head = move_head::<BYTES>(self.head, transition.direction, step)?;
+
+        self.trace
+            .push(transition.next, transition.write, self.head, head);
+        self.head = head;
+
+        Ok(())
+    }
+
+    /// Execute the given number of transitions.
+    pub fn run(&mut self, n_steps: usize) -> Result<(), SimulationError> {
+        for _ in 0..n_steps {
+            self.step()?;
+        }
+
+        Ok(())
+    }
+
+    /// Execute transitions until an accepting state is reached,
+    /// but at most the given number of transitions.
+    pub fn run_until_accepting(&mut self, max_steps: usize) -> Result<(), SimulationError> {
+        for _ in 0..max_steps {
+            if self.machine.is_accepting(self.state()) {
+                return Ok(());
+            }
+            self.step()?;
+        }
+
+        if self.machine.is_accepting(self.state()) {
+            Ok(())
+        } else {
+            Err(SimulationError::NotAccepted { steps: max_steps })
+        }
+    }
+
+    /// Execute transitions until the machine halts on an undefined transition,
+    /// but at most the given number of transitions.
+    pub fn run_until_halting(&mut self, max_steps: usize) -> Result<(), SimulationError> {
+        for _ in 0..max_steps {
+            match self.step() {
+                Err(SimulationError::Halted { .. }) => return Ok(()),
+                result => result?,
+            }
+        }
+
+        let step = self.trace.len() - 1;
+        if self
+            .machine
+            .is_defined(self.state(), self.trace.symbol(step, self.head))
+        {
+            Err(SimulationError::NotHalted { steps: max_steps })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Execute transitions until an accepting or a rejecting state is reached,
+    /// but at most the given number of transitions.
+    ///
+    /// Return whether the machine accepts.
+    pub fn run_until_decided(&mut self, max_steps: usize) -> Result<bool, SimulationError> {
+        let decision = |q| {
+            if self.machine.is_accepting(q) {
+                Some(true)
+            } else if self.machine.is_rejecting(q) {
+                Some(false)
+            } else {
+                None
+            }
+        };
+
+        for _ in 0..max_steps {
+            if let Some(accepts) = decision(self.state()) {
+                return Ok(accepts);
+            }
+            self.step()?;
+        }
+
+        decision(self.state()).ok_or(SimulationError::NotDecided { steps: max_steps })
+    }
+
+    /// Execute transitions until the last configuration repeats an earlier one,
+    /// but at most the given number of transitions.
+    ///
+    /// Return the step of the earlier configuration, which is where the cycle starts.
+    /// See [`crate::DynVerifier::verify_cycle`].
+    pub fn run_until_cycle(&mut self, max_steps: usize) -> Result<usize, SimulationError> {
+        for _ in 0..max_steps {
+            self.step()?;
+            let last = self.trace.len() - 1;
+            if let Some(t) = (0..last).find(|&t| self.trace.eq_configuration(t, last)) {
+                return Ok(t);
+            }
+        }
+
+        Err(SimulationError::NoCycle { steps: max_steps })
+    }
+
+    /// Execute transitions until the last configuration repeats an earlier one shifted by a nonzero number of cells,
+    /// but at most the given number of transitions.
+    ///
+    /// See [`Trace::translated_cycle`].
+    pub fn run_until_translated_cycle(
+        &mut self,
+        max_steps: usize,
+    ) -> Result<TranslatedCycle, SimulationError> {
+        for _ in 0..max_steps {
+            self.step()?;
+            let last = self.trace.len() - 1;
+            if let Some(cycle) = (0..last).find_map(|t| self.trace.translated_cycle(t, last)) {
+                return Ok(cycle);
+            }
+        }
+
+        Err(SimulationError::NoCycle { steps: max_steps })
+    }
+}
+
+/// Native simulator of a [`MultiTapeTableMachine`] that records a trace for each tape.
+///
+/// Each tape consists of `8 · BYTES` cells. The traces share the same states.
+pub struct MultiTapeSimulator<'a, const BYTES: usize = 32> {
+    machine: &'a MultiTapeTableMachine,
+    traces: Vec<Trace<BYTES>>,
+    heads: Vec<usize>,
+}
+
+impl<'a, const BYTES: usize> MultiTapeSimulator<'a, BYTES> {
+    /// Create a simulator that starts in the initial state, on the given tapes and with each head at the given cell.
+    ///
+    /// If there are multiple initial states, then the least one is chosen.
+    /// Each tape holds the least significant bit of each symbol; the higher bits start blank.
+    pub fn new(machine: &'a MultiTapeTableMachine, tapes: &[[u8; BYTES]], head: usize) -> Self {
+        assert_eq!(machine.n_tapes(), tapes.len(), "Expected one tape per head");
+        assert!(head < 8 * BYTES, "Cell {} is not on the tape", head);
+        let state = (0..machine.n_states())
+            .find(|&q| machine.is_initial(q))
+            .expect("There must be an initial state");
+        let traces = tapes
+            .iter()
+            .map(|&tape| Trace::start(state, tape, head, machine.symbol_bits()))
+            .collect();
+
+        Self {
+            machine,
+            traces,
+            heads: vec![head; tapes.len()],
+        }
+    }
+
+    /// Return the current state.
+    pub fn state(&self) -> usize {
+        *self.traces[0].states.last().unwrap() as usize
+    }
+
+    /// Return the current cell of each head.
+    pub fn heads(&self) -> &[usize] {
+        &self.heads
+    }
+
+    /// Return the traces of configurations so far, one for each tape.
+    pub fn traces(&self) -> &[Trace<BYTES>] {
+        &self.traces
+    }
+
+    /// Consume the simulator and return the traces of configurations, one for each tape.
+    ///
+    /// See [`util::multi_tape_computation`] to combine the traces into one computation.
+    pub fn into_traces(self) -> Vec<Trace<BYTES>> {
+        self.traces
+    }
+
+    /// Execute one transition.
+    pub fn step(&mut self) -> Result<(), SimulationError> {
+        let step = self.traces[0].len() - 1;
+        let read: Vec<u8> = self
+            .traces
+            .iter()
+            .zip(&self.heads)
+            .map(|(trace, &head)| trace.symbol(step, head))
+            .collect();
+        let transition = self.machine.transition(self.state(), &read);
+        let heads = self
+            .heads
+            .iter()
+            .zip(&transition.directions)
+            .map(|(&head, &direction)| move_head::<BYTES>(head, direction, step))
+            .collect::<Result<Vec<usize>, SimulationError>>()?;
+
+        for (tape, trace) in self.traces.iter_mut().enumerate() {
+            trace.push(
+                transition.next,
+                transition.write[tape],
+                self.heads[tape],
+                heads[tape],
+            );
+        }
+        self.heads = heads;
+
+        Ok(())
+    }
+
+    /// Execute transitions until an accepting state is reached,
+    /// but at most the given number of transitions.
+    pub fn run_until_accepting(&mut self, max_steps: usize) -> Result<(), SimulationError> {
+        for _ in 0..max_steps {
+            if self.machine.is_accepting(self.state()) {
+                return Ok(());
+            }
+            self.step()?;
+        }
+
+        if self.machine.is_accepting(self.state()) {
+            Ok(())
+        } else {
+            Err(SimulationError::NotAccepted { steps: max_steps })
+        }
+    }
+}
+
+/// Native simulator of a [`NondeterministicTableMachine`] that records the trace of its configurations
+/// and the choices of transitions that it takes.
+///
+/// The tape consists of `8 · BYTES` cells.
+pub struct NondeterministicSimulator<'a, const BYTES: usize = 32> {
+    machine: &'a NondeterministicTableMachine,
+    trace: Trace<BYTES>,
+    choices: Vec<usize>,
+    head: usize,
+}
+
+impl<'a, const BYTES: usize> NondeterministicSimulator<'a, BYTES> {
+    /// Create a simulator that starts in the initial state, on the given tape and at the given cell.
+    ///
+    /// If there are multiple initial states, then the least one is chosen.
+    /// The tape holds the least significant bit of each symbol; the higher bits start blank.
+    pub fn new(machine: &'a NondeterministicTableMachine, tape: [u8; BYTES], head: usize) -> Self {
+        assert!(head < 8 * BYTES, "Cell {} is not on the tape", head);
+        let state = (0..machine.n_states())
+            .find(|&q| machine.is_initial(q))
+            .expect("There must be an initial state");
+        let trace = Trace::start(state, tape, head, machine.symbol_bits());
+
+        Self {
+            machine,
+            trace,
+            choices: Vec::new(),
+            head,
+        }
+    }
+
+    /// Return the current state.
+    pub fn state(&self) -> usize {
+        *self.trace.states.last().unwrap() as usize
+    }
+
+    /// Return the current cell.
+    pub fn head(&self) -> usize {
+        self.head
+    }
+
+    /// Return the trace of configurations so far.
+    pub fn trace(&self) -> &Trace<BYTES> {
+        &self.trace
+    }
+
+    /// Return the choices of transitions so far, in the format of [`NondeterministicTableMachine::with_choices`].
+    pub fn choices(&self) -> &[usize] {
+        &self.choices
+    }
+
+    /// Consume the simulator and return the trace of configurations and the choices of transitions.
+    pub fn into_trace(self) -> (Trace<BYTES>, Vec<usize>) {
+        (self.trace, self.choices)
+    }
+
+    /// Execute the transition with the given choice.
+    pub fn step(&mut self, choice: usize) -> Result<(), SimulationError> {
+        let step = self.trace.len() - 1;
+        let b = self.trace.symbol(step, self.head);
+        let transition = *self
+            .machine
+            .transitions(self.state(), b)
+            .get(choice)
+            .ok_or(SimulationError::InvalidChoice { step, choice })?;
+        let head = move_head::<BYTES>(self.head, transition.direction, step)?;
+
+        self.trace
+            .push(transition.next, transition.write, self.head, head);
+        self.choices.push(choice);
+        self.head = head;
+
+        Ok(())
+    }
+
+    /// Execute the transitions with the given choices, one per step.
+    pub fn run(&mut self, choices: &[usize]) -> Result<(), SimulationError> {
+        for &choice in choices {
+            self.step(choice)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Return the cell of the head after moving in the given direction in the given step.
+fn move_head<const BYTES: usize>(
+    head: usize,
+    direction: Direction,
+    step: usize,
+) -> Result<usize, SimulationError> {
+    match direction {
+        Direction::Left => head
+            .checked_sub(1)
+            .ok_or(SimulationError::HeadOffLeftEdge { step }),
+        Direction::Right if head + 1 < 8 * BYTES => Ok(head + 1),
+        Direction::Right => Err(SimulationError::HeadOffRightEdge { step }),
+        Direction::Stay => Ok(head),
+    }
+}
+
+/// Return the cell of the given one-hot index.
+fn cell(index: &[u8]) -> usize {
+    (0..8 * index.len())
+        .find(|&cell| get(index, cell))
+        .expect("One-hot index must have a set bit")
+}
+
+/// Return the symbol at the given cell.
+fn get(tape: &[u8], cell: usize) -> bool {
+    tape[cell / 8] & (0b10000000 >> (cell % 8)) != 0
+}
+
+/// Set the symbol at the given cell.
+fn set(tape: &mut [u8], cell: usize, symbol: bool) {
+    if symbol {
+        tape[cell / 8] |= 0b10000000 >> (cell % 8);
+    } else {
+        tape[cell / 8] &= !(0b10000000 >> (cell % 8));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::computation::{
+        Computation256, Computation256Binary, Computation256Symbols, Computation512,
+        ComputationMerkle,
+    };
+    use crate::machine::{Machine, ThreeBeavers, Transition, TwoBeavers};
+    use crate::{execute, DynVerifier, TranslatedCycleVerifier, VerificationError, Verifier};
+    use simplicity::core::Context;
+    use simplicity::exec::BitMachine;
+    use std::marker::PhantomData;
+
+    /// Table of [`TwoBeavers`]
+    fn two_beavers() -> TableMachine {
+        let transitions = vec![
+            vec![
+                Transition::new(1, Direction::Right, 1),
+                Transition::new(1, Direction::Left, 1),
+            ],
+            vec![
+                Transition::new(1, Direction::Left, 0),
+                Transition::new(1, Direction::Left, 0),
+            ],
+        ];
+        TableMachine::new(transitions, &[0], &[1])
+    }
+
+    /// Table of [`ThreeBeavers`]
+    fn three_beavers() -> TableMachine {
+        let transitions = vec![
+            vec![
+                Transition::new(1, Direction::Right, 1),
+                Transition::new(1, Direction::Right, 1),
+            ],
+            vec![
+                Transition::new(0, Direction::Right, 2),
+                Transition::new(1, Direction::Right, 1),
+            ],
+            vec![
+                Transition::new(1, Direction::Left, 2),
+                Transition::new(1, Direction::Left, 0),
+            ],
+        ];
+        TableMachine::new(transitions, &[0], &[0])
+    }
+
+    #[test]
+    fn step() {
+        let machine = two_beavers();
+        let mut simulator = Simulator::new(&machine, [0; 32], 2);
+        simulator.run(5).unwrap();
+        let trace = simulator.into_trace();
+
+        assert_eq!(vec![0, 1, 0, 1, 0, 1], trace.states);
+        let tape_bytes = [
+            0b00000000, 0b00100000, 0b00110000, 0b00110000, 0b01110000, 0b11110000,
+        ];
+        let index_bytes = [
+            0b00100000, 0b00010000, 0b00100000, 0b01000000, 0b10000000, 0b01000000,
+        ];
+
+        for i in 0..trace.len() {
+            assert_eq!(tape_bytes[i], trace.tapes[i][0]);
+            assert_eq!(index_bytes[i], trace.indices[i][0]);
+            assert!(trace.tapes[i][1..].iter().all(|&byte| byte == 0));
+            assert!(trace.indices[i][1..].iter().all(|&byte| byte == 0));
+        }
+    }
+
+    #[test]
+    fn edges() {
+        let machine = two_beavers();
+
+        let mut simulator = Simulator::new(&machine, [0; 32], 0);
+        assert_eq!(
+            Err(SimulationError::HeadOffLeftEdge { step: 2 }),
+            simulator.run(3)
+        );
+
+        let mut simulator = Simulator::new(&machine, [0; 32], 255);
+        assert_eq!(
+            Err(SimulationError::HeadOffRightEdge { step: 0 }),
+            simulator.step()
+        );
+    }
+
+    #[test]
+    fn run_until_accepting() {
+        let machine = "1RB1LB_1LA1RZ".parse::<TableMachine>().unwrap();
+        let mut simulator = Simulator::new(&machine, [0; 32], 128);
+        simulator.run_until_accepting(100).unwrap();
+        assert_eq!(7, simulator.trace().len());
+        assert_eq!(2, simulator.state());
+
+        let mut simulator = Simulator::new(&machine, [0; 32], 128);
+        assert_eq!(
+            Err(SimulationError::NotAccepted { steps: 5 }),
+            simulator.run_until_accepting(5)
+        );
+    }
+
+    #[test]
+    fn verify_trace() {
+        let machine = two_beavers();
+        let mut simulator = Simulator::new(&machine, [0; 32], 2);
+        simulator.run(5).unwrap();
+        let trace = simulator.into_trace();
+
+        let mut context = Context::default();
+        let commit =
+            Verifier::<Computation256, TwoBeavers>::verify_computation(&mut context, trace.len());
+        let program = commit
+            .finalize(trace.computation256(|q| machine.state_value(q as usize)))
+            .unwrap();
+
+        let mut mac = BitMachine::for_program(&program);
+        mac.exec(&program, &()).unwrap();
+    }
+
+    #[test]
+    fn verify_trace_busy_beaver() {
+        fn verify<M: Machine>(
+            machine: &TableMachine,
+            trace: &Trace,
+            ones: u16,
+        ) -> Result<(), VerificationError> {
+            let mut context = Context::default();
+            let commit = DynVerifier::new(&PhantomData::<Computation256>, &PhantomData::<M>)
+                .with_blank_start(128)
+                .with_score(ones)
+                .verify_computation(&mut context, trace.len());
+            let program = commit
+                .finalize(trace.computation256(|q| machine.state_value(q as usize)))
+                .unwrap();
+
+            execute(&program)
+        }
+
+        let machine = two_beavers();
+        let mut simulator = Simulator::new(&machine, [0; 32], 128);
+        simulator.run(5).unwrap();
+        let trace = simulator.into_trace();
+        assert_eq!(Ok(()), verify::<TwoBeavers>(&machine, &trace, 4));
+        assert_eq!(
+            Err(VerificationError::InvalidScore),
+            verify::<TwoBeavers>(&machine, &trace, 3)
+        );
+
+        let machine = three_beavers();
+        let mut simulator = Simulator::new(&machine, [0; 32], 128);
+        // BusyBeaver(3) halts on the 14th transition, which reads 1 in the initial state and writes 1
+        simulator.run(13).unwrap();
+        assert_eq!(0, simulator.state());
+        assert_eq!(1, simulator.trace().symbol(13, simulator.head()));
+        let trace = simulator.into_trace();
+        assert_eq!(Ok(()), verify::<ThreeBeavers>(&machine, &trace, 6));
+        assert_eq!(
+            Err(VerificationError::InvalidScore),
+            verify::<ThreeBeavers>(&machine, &trace, 7)
+        );
+    }
+
+    /// Decider that accepts an even number of consecutive ones and rejects an odd number
+    fn parity() -> TableMachine {
+        let halt = |q| vec![Transition::new(0, Direction::Stay, q); 2];
+        let transitions = vec![
+            vec![
+                Transition::new(0, Direction::Stay, 2),
+                Transition::new(1, Direction::Right, 1),
+            ],
+            vec![
+                Transition::new(0, Direction::Stay, 3),
+                Transition::new(1, Direction::Right, 0),
+            ],
+            halt(2),
+            halt(3),
+        ];
+        TableMachine::new(transitions, &[0], &[2]).with_rejecting(&[3])
+    }
+
+    #[test]
+    fn verify_trace_rejection() {
+        let machine = parity();
+        let verify = |input: [u8; 32], rejection: bool| {
+            let mut simulator = Simulator::new(&machine, input, 0);
+            let accepts = simulator.run_until_decided(100).unwrap();
+            let trace = simulator.into_trace();
+
+            let mut context = Context::default();
+            let mut verifier =
+                DynVerifier::new(&PhantomData::<Computation256>, &machine).with_input(&input, 0);
+            if rejection {
+                verifier = verifier.with_rejection();
+            }
+            let program = verifier
+                .verify_computation(&mut context, trace.len())
+                .finalize(trace.computation(|q| machine.state_value(q as usize)))
+                .unwrap();
+
+            (accepts, execute(&program))
+        };
+
+        let mut odd = [0; 32];
+        odd[0] = 0b11100000;
+        let mut even = [0; 32];
+        even[0] = 0b11110000;
+
+        assert_eq!((false, Ok(())), verify(odd, true));
+        assert_eq!(
+            (false, Err(VerificationError::InvalidAcceptingState)),
+            verify(odd, false)
+        );
+        assert_eq!((true, Ok(())), verify(even, false));
+        assert_eq!(
+            (true, Err(VerificationError::InvalidRejectingState)),
+            verify(even, true)
+        );
+
+        let mut simulator = Simulator::new(&machine, odd, 0);
+        assert_eq!(
+            Err(SimulationError::NotDecided { steps: 2 }),
+            simulator.run_until_decided(2)
+        );
+    }
+
+    /// Machine with three states that halts in `C`, whose transition upon reading `0` may be defined
+    fn partial_three_states(c_0: Option<Transition>) -> TableMachine {
+        let transitions = vec![
+            vec![
+                Some(Transition::new(1, Direction::Right, 1)),
+                Some(Transition::new(1, Direction::Left, 2)),
+            ],
+            vec![Some(Transition::new(1, Direction::Left, 0)), None],
+            vec![c_0, None],
+        ];
+        TableMachine::partial(transitions, &[0], &[2])
+    }
+
+    #[test]
+    fn verify_trace_halting() {
+        let verify = |machine: &TableMachine, trace: &Trace, halting: bool, stutter: bool| {
+            let mut context = Context::default();
+            let mut verifier =
+                DynVerifier::new(&PhantomData::<Computation256>, machine).with_blank_start(128);
+            if halting {
+                verifier = verifier.with_halting();
+            }
+            if stutter {
+                verifier = verifier.with_stutter();
+            }
+            let program = verifier
+                .verify_computation(&mut context, trace.len())
+                .finalize(trace.computation(|q| machine.state_value(q as usize)))
+                .unwrap();
+
+            execute(&program)
+        };
+
+        let machine = partial_three_states(None);
+        let mut simulator = Simulator::new(&machine, [0; 32], 128);
+        simulator.run_until_halting(100).unwrap();
+        assert_eq!(Err(SimulationError::Halted { step: 3 }), simulator.step());
+        let trace = simulator.into_trace();
+        assert_eq!(4, trace.len());
+        assert_eq!(Ok(()), verify(&machine, &trace, true, false));
+        let padded = trace.stutter(trace.len() + 2);
+        assert_eq!(Ok(()), verify(&machine, &padded, true, true));
+
+        let mut simulator = Simulator::new(&machine, [0; 32], 128);
+        assert_eq!(
+            Err(SimulationError::NotHalted { steps: 2 }),
+            simulator.run_until_halting(2)
+        );
+
+        let total = partial_three_states(Some(Transition::new(1, Direction::Right, 2)));
+        let mut simulator = Simulator::new(&total, [0; 32], 128);
+        simulator.run(3).unwrap();
+        let trace = simulator.into_trace();
+        assert_eq!(Ok(()), verify(&total, &trace, false, false));
+        assert_eq!(
+            Err(VerificationError::NotHalted),
+            verify(&total, &trace, true, false)
+        );
+
+        let mut simulator = Simulator::new(&total, [0; 32], 128);
+        simulator.run(4).unwrap();
+        let trace = simulator.into_trace();
+        assert_eq!(Ok(()), verify(&total, &trace, true, false));
+        assert_eq!(
+            Err(VerificationError::UnexpectedHalt),
+            verify(&machine, &trace, false, false)
+        );
+
+        let parsed = "1RB1LB_1LA1RZ".parse::<TableMachine>().unwrap();
+        let mut simulator = Simulator::new(&parsed, [0; 32], 128);
+        simulator.run_until_halting(100).unwrap();
+        let trace = simulator.into_trace();
+        assert_eq!(Ok(()), verify(&parsed, &trace, true, false));
+
+        let undefined = "1RB1LB_1LA---".parse::<TableMachine>().unwrap();
+        let mut simulator = Simulator::new(&undefined, [0; 32], 128);
+        simulator.run_until_halting(100).unwrap();
+        let trace = simulator.into_trace();
+        assert_eq!(Ok(()), verify(&undefined, &trace, true, false));
+        assert_eq!(
+            Err(VerificationError::InvalidAcceptingState),
+            verify(&undefined, &trace, false, false)
+        );
+
+        let transitions = (0..3)
+            .map(|q| (0..2).map(|b| parsed.transition(q, b)).collect())
+            .collect();
+        let total = TableMachine::new(transitions, &[0], &[2]);
+        assert_eq!(
+            Err(VerificationError::NotHalted),
+            verify(&total, &trace, true, false)
+        );
+    }
+
+    #[test]
+    fn verify_trace_binary() {
+        let machine = two_beavers();
+        let mut simulator = Simulator::new(&machine, [0; 32], 2);
+        simulator.run(5).unwrap();
+        let trace = simulator.into_trace();
+
+        let mut context = Context::default();
+        let commit = Verifier::<Computation256Binary, TwoBeavers>::verify_computation(
+            &mut context,
+            trace.len(),
+        );
+        let program = commit
+            .finalize(trace.computation256_binary(|q| machine.state_value(q as usize)))
+            .unwrap();
+
+        let mut mac = BitMachine::for_program(&program);
+        mac.exec(&program, &()).unwrap();
+    }
+
+    #[test]
+    fn verify_trace_wide() {
+        let machine = "1RB1LB_1LA1RZ".parse::<TableMachine>().unwrap();
+
+        for head in [254, 255, 256, 257] {
+            let mut simulator = Simulator::<64>::new(&machine, [0; 64], head);
+            simulator.run_until_accepting(100).unwrap();
+            let trace = simulator.into_trace();
+
+            let mut context = Context::default();
+            let verifier = DynVerifier::new(&PhantomData::<Computation512>, &machine);
+            let commit = verifier.verify_computation(&mut context, trace.len());
+            let program = commit
+                .finalize(trace.computation(|q| machine.state_value(q as usize)))
+                .unwrap();
+
+            let mut mac = BitMachine::for_program(&program);
+            mac.exec(&program, &()).unwrap();
+        }
+    }
+
+    #[test]
+    fn verify_trace_merkle() {
+        let machine = "1RB1LB_1LA1RZ".parse::<TableMachine>().unwrap();
+        let mut simulator = Simulator::<64>::new(&machine, [0; 64], 255);
+        simulator.run_until_accepting(100).unwrap();
+        let trace = simulator.into_trace();
+
+        let mut context = Context::default();
+        let verifier = DynVerifier::new(&PhantomData::<ComputationMerkle<9>>, &machine)
+            .with_blank_start(255)
+            .with_output(trace.tapes.last().unwrap());
+        let commit = verifier.verify_computation(&mut context, trace.len());
+        let program = commit
+            .finalize(trace.computation_merkle(|q| machine.state_value(q as usize)))
+            .unwrap();
+        assert_eq!(Ok(()), execute(&program));
+
+        let mut invalid_tape = trace.clone();
+        let written = cell(&trace.indices[1]);
+        invalid_tape.tapes[2][written / 8] ^= 0b10000000 >> (written % 8);
+        let program = commit
+            .finalize(invalid_tape.computation_merkle(|q| machine.state_value(q as usize)))
+            .unwrap();
+        assert_eq!(Err(VerificationError::InvalidTape), execute(&program));
+    }
+
+    #[test]
+    fn verify_trace_symbols() {
+        let machine = "1RB2LB1RZ_2LA2RB1LB".parse::<TableMachine>().unwrap();
+        let mut simulator = Simulator::new(&machine, [0; 32], 128);
+        simulator.run_until_accepting(100).unwrap();
+        let trace = simulator.into_trace();
+        assert_eq!(39, trace.len());
+
+        let last = trace.len() - 1;
+        let nonzero = (0..256)
+            .filter(|&cell| trace.symbol(last, cell) != 0)
+            .count();
+        assert_eq!(9, nonzero);
+
+        let mut context = Context::default();
+        let verifier = DynVerifier::new(&PhantomData::<Computation256Symbols<2>>, &machine)
+            .with_blank_start(128)
+            .with_output(&trace.tape_planes(last));
+        let commit = verifier.verify_computation(&mut context, trace.len());
+        let program = commit
+            .finalize(trace.computation256_symbols(|q| machine.state_value(q as usize)))
+            .unwrap();
+        assert_eq!(Ok(()), execute(&program));
+
+        let mut invalid_tape = trace.clone();
+        let written = cell(&trace.indices[1]);
+        invalid_tape.planes[2][0][written / 8] ^= 0b10000000 >> (written % 8);
+        let program = commit
+            .finalize(invalid_tape.computation256_symbols(|q| machine.state_value(q as usize)))
+            .unwrap();
+        assert_eq!(Err(VerificationError::InvalidTape), execute(&program));
+    }
+
+    #[test]
+    fn verify_trace_stay() {
+        let machine = "1RB0NB_1LA1RZ".parse::<TableMachine>().unwrap();
+        let mut simulator = Simulator::new(&machine, [0; 32], 128);
+        simulator.run_until_accepting(100).unwrap();
+        let trace = simulator.into_trace();
+
+        let stay = (0..trace.len() - 1)
+            .find(|&t| trace.indices[t] == trace.indices[t + 1])
+            .unwrap();
+
+        let mut context = Context::default();
+        let verifier = DynVerifier::new(&PhantomData::<Computation256>, &machine);
+        let commit = verifier.verify_computation(&mut context, trace.len());
+        let program = commit
+            .finalize(trace.computation(|q| machine.state_value(q as usize)))
+            .unwrap();
+        assert_eq!(Ok(()), execute(&program));
+
+        let mut invalid_index = trace.clone();
+        invalid_index.indices[stay + 1] = one_hot(cell(&trace.indices[stay]) + 1);
+        let program = commit
+            .finalize(invalid_index.computation(|q| machine.state_value(q as usize)))
+            .unwrap();
+        assert_eq!(Err(VerificationError::InvalidIndex), execute(&program));
+    }
+
+    #[test]
+    fn verify_trace_cycle() {
+        let machine = "1RB---_0LC---_---1RB".parse::<TableMachine>().unwrap();
+        let mut simulator = Simulator::new(&machine, [0; 32], 128);
+        let cycle_start = simulator.run_until_cycle(100).unwrap();
+        let trace = simulator.into_trace();
+        assert_eq!((1, 4), (cycle_start, trace.len()));
+
+        let mut context = Context::default();
+        let verifier = DynVerifier::new(&PhantomData::<Computation256>, &machine)
+            .with_blank_start(128)
+            .verify_cycle(&mut context, trace.len(), cycle_start);
+        let program = verifier
+            .finalize(trace.computation(|q| machine.state_value(q as usize)))
+            .unwrap();
+        assert_eq!(Ok(()), execute(&program));
+
+        let mut context = Context::default();
+        let verifier = DynVerifier::new(&PhantomData::<Computation256>, &machine)
+            .with_blank_start(128)
+            .verify_cycle(&mut context, trace.len(), 0);
+        let program = verifier
+            .finalize(trace.computation(|q| machine.state_value(q as usize)))
+            .unwrap();
+        assert_eq!(Err(VerificationError::InvalidCycle), execute(&program));
+
+        let mut context = Context::default();
+        let halting = TableMachine::new(
+            vec![vec![
+                Transition::new(0, Direction::Stay, 0),
+                Transition::new(1, Direction::Stay, 0),
+            ]],
+            &[0],
+            &[0],
+        );
+        let mut simulator = Simulator::new(&halting, [0; 32], 128);
+        assert_eq!(Ok(0), simulator.run_until_cycle(100));
+        let trace = simulator.into_trace();
+        let verifier = DynVerifier::new(&PhantomData::<Computation256>, &halting).verify_cycle(
+            &mut context,
+            trace.len(),
+            0,
+        );
+        let program = verifier
+            .finalize(trace.computation(|q| halting.state_value(q as usize)))
+            .unwrap();
+        assert_eq!(
+            Err(VerificationError::UnexpectedAcceptingState),
+            execute(&program)
+        );
+
+        let mut context = Context::default();
+        let stay = vec![
+            Transition::new(0, Direction::Stay, 0),
+            Transition::new(1, Direction::Stay, 0),
+        ];
+        let rejecting =
+            TableMachine::new(vec![stay.clone(), stay], &[0], &[1]).with_rejecting(&[0]);
+        let mut simulator = Simulator::new(&rejecting, [0; 32], 128);
+        assert_eq!(Ok(0), simulator.run_until_cycle(100));
+        let trace = simulator.into_trace();
+        let verifier = DynVerifier::new(&PhantomData::<Computation256>, &rejecting).verify_cycle(
+            &mut context,
+            trace.len(),
+            0,
+        );
+        let program = verifier
+            .finalize(trace.computation(|q| rejecting.state_value(q as usize)))
+            .unwrap();
+        assert_eq!(
+            Err(VerificationError::UnexpectedRejectingState),
+            execute(&program)
+        );
+    }
+
+    #[test]
+    fn verify_trace_binary_cycle() {
+        let machine = "1RB---_0LC---_---1RB".parse::<TableMachine>().unwrap();
+        let mut simulator = Simulator::new(&machine, [0; 32], 128);
+        let cycle_start = simulator.run_until_cycle(100).unwrap();
+        let trace = simulator.into_trace();
+
+        let mut context = Context::default();
+        let verifier = DynVerifier::new(&PhantomData::<Computation256Binary>, &machine)
+            .with_blank_start(128)
+            .verify_cycle(&mut context, trace.len(), cycle_start);
+        let program = verifier
+            .finalize(trace.computation256_binary(|q| machine.state_value(q as usize)))
+            .unwrap();
+        assert_eq!(Ok(()), execute(&program));
+
+        // Moving right forever visits each cell once,
+        // so the configurations only repeat if the index wraps around the tape
+        let machine = "0RA0RA".parse::<TableMachine>().unwrap();
+        let wrapped = Trace::new(
+            vec![0; 257],
+            vec![[0; 32]; 257],
+            (0..257).map(|cell| one_hot(cell % 256)).collect(),
+        );
+        let mut context = Context::default();
+        let verifier = DynVerifier::new(&PhantomData::<Computation256Binary>, &machine)
+            .with_blank_start(0)
+            .verify_cycle(&mut context, wrapped.len(), 0);
+        let program = verifier
+            .finalize(wrapped.computation256_binary(|q| machine.state_value(q as usize)))
+            .unwrap();
+        assert_eq!(Err(VerificationError::HeadOffRightEdge), execute(&program));
+    }
+
+    #[test]
+    fn verify_trace_translated_cycle() {
+        let machine = "0LB0RB_1RA1LB".parse::<TableMachine>().unwrap();
+        let mut simulator = Simulator::new(&machine, [0; 32], 128);
+        let cycle = simulator.run_until_translated_cycle(100).unwrap();
+        let trace = simulator.into_trace();
+        assert_eq!(1, cycle.shift);
+        assert_eq!(cycle.end + 1, trace.len());
+
+        let verify = |cycle: &TranslatedCycle| {
+            let mut context = Context::default();
+            let program = TranslatedCycleVerifier::new(&machine)
+                .with_blank_start(128)
+                .verify_translated_cycle(&mut context, cycle)
+                .finalize(trace.computation(|q| machine.state_value(q as usize)))
+                .unwrap();
+            execute(&program)
+        };
+        assert_eq!(Ok(()), verify(&cycle));
+
+        let invalid_shift = TranslatedCycle {
+            shift: cycle.shift + 1,
+            ..cycle
+        };
+        assert_eq!(Err(VerificationError::InvalidCycle), verify(&invalid_shift));
+
+        let invalid_bound = TranslatedCycle {
+            bound: cycle.bound + 1,
+            ..cycle
+        };
+        assert_eq!(Err(VerificationError::InvalidCycle), verify(&invalid_bound));
+
+        // Repeating a pattern to the left runs off the left edge of the tape instead of cycling
+        let machine = "1LB0LA_1RB1RA".parse::<TableMachine>().unwrap();
+        let mut simulator = Simulator::new(&machine, [0; 32], 128);
+        assert!(matches!(
+            simulator.run_until_translated_cycle(1000),
+            Err(SimulationError::HeadOffLeftEdge { .. })
+        ));
+    }
+
+    /// Machine that writes any string of ones and twos and halts on a blank cell
+    fn guess() -> NondeterministicTableMachine {
+        use Direction::*;
+        let t = Transition::new;
+        let transitions = vec![
+            vec![
+                vec![t(1, Right, 0), t(2, Right, 0), t(0, Stay, 1)],
+                vec![t(1, Right, 0)],
+                vec![t(2, Right, 0)],
+            ],
+            vec![
+                vec![t(0, Stay, 1)],
+                vec![t(1, Stay, 1)],
+                vec![t(2, Stay, 1)],
+            ],
+        ];
+        NondeterministicTableMachine::new(transitions, &[0], &[1])
+    }
+
+    #[test]
+    fn verify_trace_nondeterministic() {
+        let machine = guess();
+        let mut simulator = NondeterministicSimulator::new(&machine, [0; 32], 128);
+        simulator.run(&[0, 1, 0, 2]).unwrap();
+        assert_eq!(1, simulator.state());
+        assert_eq!(
+            Err(SimulationError::InvalidChoice { step: 4, choice: 1 }),
+            simulator.step(1)
+        );
+        let (trace, choices) = simulator.into_trace();
+        let last = trace.len() - 1;
+        let symbols: Vec<u8> = (128..132).map(|cell| trace.symbol(last, cell)).collect();
+        assert_eq!(vec![1, 2, 1, 0], symbols);
+
+        let mut context = Context::default();
+        let verifier = DynVerifier::new(&PhantomData::<Computation256Symbols<2>>, &machine)
+            .with_blank_start(128)
+            .with_output(&trace.tape_planes(last));
+        let commit = verifier.verify_computation(&mut context, trace.len());
+        let computation = || trace.computation256_symbols(|q| machine.state_value(q as usize));
+        let program = commit
+            .finalize(machine.with_choices(computation(), &choices))
+            .unwrap();
+        assert_eq!(Ok(()), execute(&program));
+
+        let program = commit
+            .finalize(machine.with_choices(computation(), &[3, 1, 0, 2]))
+            .unwrap();
+        assert_eq!(Err(VerificationError::InvalidChoice), execute(&program));
+
+        let program = commit
+            .finalize(machine.with_choices(computation(), &[1, 1, 0, 2]))
+            .unwrap();
+        assert_eq!(Err(VerificationError::InvalidTape), execute(&program));
+    }
+}
@@ -1,3 +1,4 @@
+use crate::computation::INDEX_BITS;
 use crate::jet::application::Turing;
 use simplicity::bitwriter::BitWriter;
 use simplicity::core::{Context, Value};
@@ -55,6 +56,206 @@ pub fn eq_22(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
     CommitNode::comp(context, first_equal_and_second, cond_second_equal_or_false).unwrap()
 }
 
+/// Check equality of `n`-bit strings.
+///
+/// The strings are nested to the right: `2^n = 2 × 2^(n - 1)` and `2^1 = 2`.
+///
+/// `eq_bits: 2^n × 2^n → 2`
+pub fn eq_bits(context: &mut Context<Turing>, n: usize) -> Rc<CommitNode<Turing>> {
+    assert!(n > 0, "Bit strings must be nonempty");
+
+    if n == 1 {
+        return eq_2(context);
+    }
+
+    // 2 → 2
+    let iden_2 = CommitNode::iden(context).unwrap();
+    // 2 × 2^(n - 1) → 2
+    let take_2_rest = CommitNode::take(context, iden_2).unwrap();
+    // 2^(n - 1) → 2^(n - 1)
+    let iden_rest = CommitNode::iden(context).unwrap();
+    // 2 × 2^(n - 1) → 2^(n - 1)
+    let drop_2_rest = CommitNode::drop(context, iden_rest).unwrap();
+    // 2^n × 2^n → 2
+    let first_first = CommitNode::take(context, take_2_rest.clone()).unwrap();
+    // 2^n × 2^n → 2
+    let second_first = CommitNode::drop(context, take_2_rest).unwrap();
+    // 2^n × 2^n → 2^(n - 1)
+    let first_rest = CommitNode::take(context, drop_2_rest.clone()).unwrap();
+    // 2^n × 2^n → 2^(n - 1)
+    let second_rest = CommitNode::drop(context, drop_2_rest).unwrap();
+
+    // 2^n × 2^n → 2 × 2
+    let first_bit = CommitNode::pair(context, first_first, second_first).unwrap();
+    // 2 × 2 → 2
+    let eq_2 = eq_2(context);
+    // 2^n × 2^n → 2
+    let first_bit_equal = CommitNode::comp(context, first_bit, eq_2).unwrap();
+    // 2^n × 2^n → 2^(n - 1) × 2^(n - 1)
+    let rest = CommitNode::pair(context, first_rest, second_rest).unwrap();
+    // 2^n × 2^n → 2 × (2^(n - 1) × 2^(n - 1))
+    let first_equal_and_rest = CommitNode::pair(context, first_bit_equal, rest).unwrap();
+
+    // 2^(n - 1) × 2^(n - 1) → 2
+    let eq_rest = eq_bits(context, n - 1);
+    // 2^(n - 1) × 2^(n - 1) → 2
+    let bit_false = CommitNode::bit_false(context).unwrap();
+    // 2 × (2^(n - 1) × 2^(n - 1)) → 2
+    let cond_rest_equal_or_false = CommitNode::cond(context, eq_rest, bit_false).unwrap();
+
+    // 2^n × 2^n → 2
+    CommitNode::comp(context, first_equal_and_rest, cond_rest_equal_or_false).unwrap()
+}
+
+/// Check equality of `n`-bit words.
+///
+/// The words are balanced, as the words of the jets: `2^n = 2^(n / 2) × 2^(n / 2)` and `2^1 = 2`.
+///
+/// `eq_word: 2^n × 2^n → 2`
+pub fn eq_word(context: &mut Context<Turing>, n: usize) -> Rc<CommitNode<Turing>> {
+    assert!(n.is_power_of_two(), "Word length must be a power of two");
+
+    if n == 1 {
+        return eq_2(context);
+    }
+
+    // 2^(n / 2) → 2^(n / 2)
+    let iden_half = CommitNode::iden(context).unwrap();
+    // 2^n → 2^(n / 2)
+    let take_half = CommitNode::take(context, iden_half.clone()).unwrap();
+    // 2^n → 2^(n / 2)
+    let drop_half = CommitNode::drop(context, iden_half).unwrap();
+    // 2^n × 2^n → 2^(n / 2)
+    let first_high = CommitNode::take(context, take_half.clone()).unwrap();
+    // 2^n × 2^n → 2^(n / 2)
+    let second_high = CommitNode::drop(context, take_half).unwrap();
+    // 2^n × 2^n → 2^(n / 2)
+    let first_low = CommitNode::take(context, drop_half.clone()).unwrap();
+    // 2^n × 2^n → 2^(n / 2)
+    let second_low = CommitNode::drop(context, drop_half).unwrap();
+
+    // 2^(n / 2) × 2^(n / 2) → 2
+    let eq_half = eq_word(context, n / 2);
+    // 2^n × 2^n → 2^(n / 2) × 2^(n / 2)
+    let high = CommitNode::pair(context, first_high, second_high).unwrap();
+    // 2^n × 2^n → 2
+    let high_equal = CommitNode::comp(context, high, eq_half.clone()).unwrap();
+    // 2^n × 2^n → 2^(n / 2) × 2^(n / 2)
+    let low = CommitNode::pair(context, first_low, second_low).unwrap();
+    // 2^n × 2^n → 2 × (2^(n / 2) × 2^(n / 2))
+    let high_equal_and_low = CommitNode::pair(context, high_equal, low).unwrap();
+
+    // 2^(n / 2) × 2^(n / 2) → 2
+    let bit_false = CommitNode::bit_false(context).unwrap();
+    // 2 × (2^(n / 2) × 2^(n / 2)) → 2
+    let cond_low_equal_or_false = CommitNode::cond(context, eq_half, bit_false).unwrap();
+
+    // 2^n × 2^n → 2
+    CommitNode::comp(context, high_equal_and_low, cond_low_equal_or_false).unwrap()
+}
+
+/// Encode the number `x` as `n`-bit string, most significant bit first.
+///
+/// The string is nested to the right, as in [`eq_bits`].
+pub fn bits_value(x: usize, n: usize) -> Value {
+    assert!(n > 0, "Bit strings must be nonempty");
+    let bit = Value::u1(((x >> (n - 1)) & 1) as u8);
+
+    if n == 1 {
+        bit
+    } else {
+        Value::prod(bit, bits_value(x, n - 1))
+    }
+}
+
+/// Return the one-hot index of the given cell.
+///
+/// The first cell is the most significant bit of the first byte.
+pub fn one_hot<const BYTES: usize>(cell: usize) -> [u8; BYTES] {
+    assert!(cell < 8 * BYTES, "Cell {} is not on the tape", cell);
+    let mut index = [0; BYTES];
+    index[cell / 8] = 0b10000000 >> (cell % 8);
+    index
+}
+
+/// Encode the given 256-bit words as balanced tree: `2^512 = 2^256 × 2^256` and so on.
+///
+/// The number of words must be a power of two.
+pub fn words_value<'a, I>(words: I) -> Value
+where
+    I: IntoIterator<Item = &'a [u8; 32]>,
+{
+    let mut values: Vec<Value> = words
+        .into_iter()
+        .map(|word| Value::u256_from_slice(word))
+        .collect();
+    assert!(
+        values.len().is_power_of_two(),
+        "Number of words must be a power of two"
+    );
+
+    while values.len() > 1 {
+        values = values
+            .chunks(2)
+            .map(|pair| Value::prod(pair[0].clone(), pair[1].clone()))
+            .collect();
+    }
+
+    values.pop().unwrap()
+}
+
+/// Encode the given 256-bit planes as nested to the right: `2^512 = 2^256 × 2^256`, `2^768 = 2^256 × 2^512` and so on.
+///
+/// There must be at least one plane.
+pub fn planes_value<'a, I>(planes: I) -> Value
+where
+    I: IntoIterator<Item = &'a [u8; 32]>,
+    I::IntoIter: DoubleEndedIterator,
+{
+    let mut planes = planes
+        .into_iter()
+        .rev()
+        .map(|plane| Value::u256_from_slice(plane));
+    let last = planes.next().expect("There must be at least one plane");
+
+    planes.fold(last, |rest, plane| Value::prod(plane, rest))
+}
+
+/// Combine computations of the individual tapes of a multi-tape machine into one computation,
+/// as witness for a program commitment of [`crate::MultiTapeVerifier`].
+///
+/// Each computation consists of configurations `M × (L × K)` with the same states.
+/// The combined configurations are `M × ((L × K) × (… × (L × K)))`, starting with the first tape.
+pub fn multi_tape_computation<I>(mut tapes: Vec<I>) -> impl Iterator<Item = Value>
+where
+    I: Iterator<Item = Value>,
+{
+    assert!(!tapes.is_empty(), "There must be at least one tape");
+
+    std::iter::from_fn(move || {
+        let mut state = None;
+        let mut tape_indices = Vec::with_capacity(tapes.len());
+
+        for tape in tapes.iter_mut() {
+            match tape.next()? {
+                Value::Prod(q, tape_index) => {
+                    state.get_or_insert(*q);
+                    tape_indices.push(*tape_index);
+                }
+                _ => panic!("Configuration must consist of state, tape and index"),
+            }
+        }
+
+        let last = tape_indices.pop().unwrap();
+        let tape_indices = tape_indices
+            .into_iter()
+            .rev()
+            .fold(last, |rest, tape_index| Value::prod(tape_index, rest));
+
+        Some(Value::prod(state.unwrap(), tape_indices))
+    })
+}
+
 /// Create a computation from states, tapes and indices, as witness for a program commitment.
 pub fn computation256_of<'a, S, T, I, F>(
     states: S,
@@ -78,6 +279,34 @@ where
         })
 }
 
+/// Create a computation from states, tapes and binary indices, as witness for a program commitment.
+///
+/// Indices are cells of the tape, which are encoded as [`INDEX_BITS`]-bit strings via [`bits_value`].
+pub fn computation256_binary_of<'a, S, T, I, F>(
+    states: S,
+    tapes: T,
+    cells: I,
+    state_to_value: F,
+) -> impl Iterator<Item = Value> + 'a
+where
+    S: Iterator<Item = &'a u8> + 'a,
+    T: Iterator<Item = &'a [u8; 32]> + 'a,
+    I: Iterator<Item = u8> + 'a,
+    F: (Fn(u8) -> Value) + 'a,
+{
+    states
+        .zip(tapes.zip(cells))
+        .map(move |(state, (tape, cell))| {
+            Value::prod(
+                state_to_value(*state),
+                Value::prod(
+                    Value::u256_from_slice(tape),
+                    bits_value(cell as usize, INDEX_BITS),
+                ),
+            )
+        })
+}
+
 /// Encode the given program commitment as base64 string.
 pub fn encode_base64(commit: &CommitNode<Turing>) -> String {
     let mut program_bytes = Vec::new();
@@ -137,4 +366,46 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn eq_bits() {
+        let mut context = Context::default();
+        let program = super::eq_bits(&mut context, 3)
+            .finalize(std::iter::empty())
+            .unwrap();
+        println!("{}", program.ty);
+
+        for a in 0..8 {
+            for b in 0..8 {
+                let input = Value::prod(bits_value(a, 3), bits_value(b, 3));
+                let eq = if a == b { Value::u1(1) } else { Value::u1(0) };
+
+                let mut mac = BitMachine::for_program(&program);
+                mac.input(&input);
+                let output = mac.exec(&program, &()).unwrap();
+                assert_eq!(eq, output);
+            }
+        }
+    }
+
+    #[test]
+    fn eq_word() {
+        let mut context = Context::default();
+        let program = super::eq_word(&mut context, 16)
+            .finalize(std::iter::empty())
+            .unwrap();
+        println!("{}", program.ty);
+
+        for a in [0, 1, 4, 0x0100, 0xffff] {
+            for b in [0, 1, 4, 0x0100, 0xffff] {
+                let input = Value::prod(Value::u16(a), Value::u16(b));
+                let eq = if a == b { Value::u1(1) } else { Value::u1(0) };
+
+                let mut mac = BitMachine::for_program(&program);
+                mac.input(&input);
+                let output = mac.exec(&program, &()).unwrap();
+                assert_eq!(eq, output);
+            }
+        }
+    }
 }
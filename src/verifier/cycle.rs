@@ -0,0 +1,543 @@
+use super::{DynVerifier, VerificationError};
+use crate::computation::Computation256;
+use crate::jet;
+use crate::jet::application::Turing;
+use crate::machine::DynMachine;
+use crate::util;
+use simplicity::core::{Context, Value};
+use simplicity::CommitNode;
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+/// Number of cells of the tapes of [`Computation256`]
+const CELLS: usize = 256;
+
+/// Certificate that a Turing machine is a translated cycler,
+/// i.e., that it repeats a configuration shifted by a fixed number of cells to the right.
+///
+/// The configuration at step `end` equals the configuration at step `start` shifted by `shift` cells,
+/// on the part of the tape that begins at the `bound` cell and extends to the right.
+/// Between the two steps, the head doesn't move left of the `bound` cell,
+/// so the machine repeats the same steps, shifted by `shift` cells each time, forever.
+///
+/// Only shifts to the right are certified, because the tape extends indefinitely only to the right:
+/// a machine that repeats its steps further and further to the left eventually moves off the left edge.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct TranslatedCycle {
+    /// Step of the configuration at the start of the cycle
+    pub start: usize,
+    /// Step of the shifted configuration at the end of the cycle
+    pub end: usize,
+    /// Number of cells of the shift to the right
+    pub shift: usize,
+    /// Cell that the head doesn't move left of during the cycle
+    pub bound: usize,
+}
+
+/// Return the tape that is one at the cells that satisfy the predicate and zero elsewhere.
+fn mask<F: Fn(usize) -> bool>(predicate: F) -> [u8; CELLS / 8] {
+    let mut mask = [0; CELLS / 8];
+    for cell in (0..CELLS).filter(|&cell| predicate(cell)) {
+        mask[cell / 8] |= 0b10000000 >> (cell % 8);
+    }
+    mask
+}
+
+impl<'a> DynVerifier<'a> {
+    /// Check that the options of the verifier can be combined with a cycle.
+    ///
+    /// Cycles prove that a deterministic machine runs forever,
+    /// so they cannot be combined with options that constrain the end of a halting computation.
+    fn assert_cycle_options(&self, context: &mut Context<Turing>) {
+        assert!(
+            !self.stutter,
+            "Cycles cannot be combined with stutter steps"
+        );
+        assert!(
+            !self.rejection,
+            "Cycles cannot be combined with a rejecting end"
+        );
+        assert!(
+            !self.halting,
+            "Cycles cannot be combined with a halting end"
+        );
+        assert!(
+            self.output.is_none(),
+            "Cycles cannot be combined with an output tape"
+        );
+        assert!(
+            self.score.is_none(),
+            "Cycles cannot be combined with a score"
+        );
+        assert!(
+            self.machine.allowed(context).is_none(),
+            "Nondeterministic machines may leave the cycle"
+        );
+    }
+
+    /// Return a program that checks that the state of the current configuration is neither accepting nor rejecting.
+    ///
+    /// `C × C → 1`
+    fn verify_not_final(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        let iden_m = CommitNode::iden(context).unwrap();
+        let take_m_lk = CommitNode::take(context, iden_m).unwrap();
+        let q = CommitNode::take(context, take_m_lk).unwrap();
+
+        let accepting = self.machine.accepting(context);
+        let is_accepting = CommitNode::comp(context, q.clone(), accepting).unwrap();
+        let check_not_accepting = CommitNode::not(context, is_accepting).unwrap();
+        let verify_not_accepting = CommitNode::assert(
+            context,
+            check_not_accepting,
+            VerificationError::UnexpectedAcceptingState.cmr(),
+        )
+        .unwrap();
+
+        match self.machine.rejecting(context) {
+            None => verify_not_accepting,
+            Some(rejecting) => {
+                let is_rejecting = CommitNode::comp(context, q, rejecting).unwrap();
+                let check_not_rejecting = CommitNode::not(context, is_rejecting).unwrap();
+                let verify_not_rejecting = CommitNode::assert(
+                    context,
+                    check_not_rejecting,
+                    VerificationError::UnexpectedRejectingState.cmr(),
+                )
+                .unwrap();
+
+                CommitNode::pair(context, verify_not_accepting, verify_not_rejecting).unwrap()
+            }
+        }
+    }
+
+    /// Return a program that checks that two configurations are equal.
+    ///
+    /// `C × C → 1`
+    fn verify_repetition(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        let iden_m = CommitNode::iden(context).unwrap();
+        let take_m_lk = CommitNode::take(context, iden_m).unwrap();
+        let q = CommitNode::take(context, take_m_lk.clone()).unwrap();
+        let q_prime = CommitNode::drop(context, take_m_lk).unwrap();
+        let pair_q_q_prime = CommitNode::pair(context, q, q_prime).unwrap();
+        let eq_state = self.machine.eq_state(context);
+        let check_state = CommitNode::comp(context, pair_q_q_prime, eq_state).unwrap();
+        let verify_state =
+            CommitNode::assert(context, check_state, VerificationError::InvalidCycle.cmr())
+                .unwrap();
+
+        let iden_k = CommitNode::iden(context).unwrap();
+        let drop_l_k = CommitNode::drop(context, iden_k).unwrap();
+        let drop_m_lk = CommitNode::drop(context, drop_l_k).unwrap();
+        let i = CommitNode::take(context, drop_m_lk.clone()).unwrap();
+        let i_prime = CommitNode::drop(context, drop_m_lk).unwrap();
+        let pair_i_i_prime = CommitNode::pair(context, i, i_prime).unwrap();
+        let eq_index = self.computation.eq_index(context);
+        let check_index = CommitNode::comp(context, pair_i_i_prime, eq_index).unwrap();
+        let verify_index =
+            CommitNode::assert(context, check_index, VerificationError::InvalidCycle.cmr())
+                .unwrap();
+        let verify_state_index = CommitNode::pair(context, verify_state, verify_index).unwrap();
+
+        let iden_l = CommitNode::iden(context).unwrap();
+        let take_l_k = CommitNode::take(context, iden_l).unwrap();
+        let drop_m_lk = CommitNode::drop(context, take_l_k).unwrap();
+        let w = CommitNode::take(context, drop_m_lk.clone()).unwrap();
+        let w_prime = CommitNode::drop(context, drop_m_lk).unwrap();
+        let pair_w_w_prime = CommitNode::pair(context, w, w_prime).unwrap();
+        let eq_tape = self.computation.eq_tape(context);
+        let check_tape = CommitNode::comp(context, pair_w_w_prime, eq_tape).unwrap();
+        let verify_tape =
+            CommitNode::assert(context, check_tape, VerificationError::InvalidCycle.cmr()).unwrap();
+
+        CommitNode::pair(context, verify_state_index, verify_tape).unwrap()
+    }
+
+    /// Return a program that verifies a computation of `n_steps` that ends in a cycle from `cycle_start`.
+    ///
+    /// Each pair of consecutive configurations is verified as a step of a computation
+    /// whose first configuration is neither accepting nor rejecting.
+    /// Pairs from `cycle_start` on are additionally checked by `verify_cycle_step`, if there is one.
+    /// The configuration at `cycle_start` and the last configuration are checked by `verify_end`.
+    fn verify_cycle_witnesses(
+        &self,
+        context: &mut Context<Turing>,
+        n_steps: usize,
+        cycle_start: usize,
+        verify_cycle_step: Option<Rc<CommitNode<Turing>>>,
+        verify_end: Rc<CommitNode<Turing>>,
+    ) -> Rc<CommitNode<Turing>> {
+        assert!(
+            cycle_start + 1 < n_steps,
+            "The cycle must start before the last configuration"
+        );
+
+        let verify_transition = self.verify_step(context);
+        let verify_not_final = self.verify_not_final(context);
+        let verify_step = CommitNode::pair(context, verify_not_final, verify_transition).unwrap();
+        let verify_cycle_step = match verify_cycle_step {
+            None => verify_step.clone(),
+            Some(verify_cycle_step) => {
+                CommitNode::pair(context, verify_step.clone(), verify_cycle_step).unwrap()
+            }
+        };
+        let verify_first = self.verify_first(context);
+        // C × C → 1 for configurations `t` and `t + 1`
+        let verify_pair = |context: &mut Context<Turing>, t: usize| {
+            let verify = if cycle_start <= t {
+                verify_cycle_step.clone()
+            } else {
+                verify_step.clone()
+            };
+            if t == 0 {
+                CommitNode::pair(context, verify_first.clone(), verify).unwrap()
+            } else {
+                verify
+            }
+        };
+
+        let iden_c = CommitNode::iden(context).unwrap();
+        let drop_c_c = CommitNode::drop(context, iden_c).unwrap();
+        // C × (C × C) → C
+        let iden_c = CommitNode::iden(context).unwrap();
+        let saved_c = CommitNode::take(context, iden_c).unwrap();
+        let last_c = CommitNode::drop(context, drop_c_c.clone()).unwrap();
+
+        // The prefix outputs the last pair of configurations that it has read.
+        // From the start of the cycle on, it also outputs the configuration at the start of the cycle:
+        // 1 → C × C, and later 1 → C × (C × C)
+        let witness_0 = CommitNode::witness(context).unwrap();
+        let witness_1 = CommitNode::witness(context).unwrap();
+        let mut verify_prefix = CommitNode::pair(context, witness_0, witness_1).unwrap();
+        let mut saved = false;
+
+        for i in 1..n_steps {
+            if !saved && cycle_start <= i {
+                // C × C → C
+                let c = if cycle_start == i {
+                    drop_c_c.clone()
+                } else {
+                    let iden_c = CommitNode::iden(context).unwrap();
+                    CommitNode::take(context, iden_c).unwrap()
+                };
+                let iden_c_c = CommitNode::iden(context).unwrap();
+                // C × C → C × (C × C)
+                let save = CommitNode::pair(context, c, iden_c_c).unwrap();
+                verify_prefix = CommitNode::comp(context, verify_prefix, save).unwrap();
+                saved = true;
+            }
+            if i + 1 == n_steps {
+                break;
+            }
+
+            let verify = verify_pair(context, i - 1);
+            let next_witness = CommitNode::witness(context).unwrap();
+            let verify_next_witness = CommitNode::comp(context, verify, next_witness).unwrap();
+            // C × C → C × C
+            let mut next_pair =
+                CommitNode::pair(context, drop_c_c.clone(), verify_next_witness).unwrap();
+            if saved {
+                // C × (C × C) → C × (C × C)
+                let drop_next_pair = CommitNode::drop(context, next_pair).unwrap();
+                next_pair = CommitNode::pair(context, saved_c.clone(), drop_next_pair).unwrap();
+            }
+
+            verify_prefix = CommitNode::comp(context, verify_prefix, next_pair).unwrap();
+        }
+
+        // C × (C × C) → 1
+        let verify_last_step = verify_pair(context, n_steps - 2);
+        let drop_verify_last_step = CommitNode::drop(context, verify_last_step).unwrap();
+        // C × (C × C) → C × C
+        let pair_c_c_prime = CommitNode::pair(context, saved_c, last_c).unwrap();
+        let verify_end = CommitNode::comp(context, pair_c_c_prime, verify_end).unwrap();
+        let verify_last = CommitNode::pair(context, drop_verify_last_step, verify_end).unwrap();
+
+        CommitNode::comp(context, verify_prefix, verify_last).unwrap()
+    }
+
+    /// Return a program that verifies that the Turing machine never halts,
+    /// given a computation of `n_steps` whose last configuration repeats the configuration at `cycle_start`.
+    ///
+    /// Besides the usual checks of the first configuration and of each step,
+    /// the program checks that no configuration is in an accepting or rejecting state.
+    /// Because the machine is deterministic, it then repeats the cycle forever without halting.
+    /// This relies on the computation format failing when the head moves off the edge of the tape
+    /// (see [`crate::computation::Computation::inc_index`]), so the cycle cannot wrap around the tape.
+    /// Panics for nondeterministic machines, which may leave the cycle,
+    /// and for options that constrain the end of a halting computation
+    /// ([`DynVerifier::with_output`], [`DynVerifier::with_score`], [`DynVerifier::with_rejection`],
+    /// [`DynVerifier::with_halting`] and [`DynVerifier::with_stutter`]).
+    ///
+    /// The program fails if the validation fails and does nothing otherwise.
+    pub fn verify_cycle(
+        &self,
+        context: &mut Context<Turing>,
+        n_steps: usize,
+        cycle_start: usize,
+    ) -> Rc<CommitNode<Turing>> {
+        self.assert_cycle_options(context);
+        let verify_repetition = self.verify_repetition(context);
+        self.verify_cycle_witnesses(context, n_steps, cycle_start, None, verify_repetition)
+    }
+
+    /// Return a program that checks that the index of the current configuration isn't left of the bound of the cycle.
+    ///
+    /// `C × C → 1`
+    fn verify_bound(
+        &self,
+        context: &mut Context<Turing>,
+        cycle: &TranslatedCycle,
+    ) -> Rc<CommitNode<Turing>> {
+        let iden_k = CommitNode::iden(context).unwrap();
+        let drop_l_k = CommitNode::drop(context, iden_k).unwrap();
+        let drop_m_lk = CommitNode::drop(context, drop_l_k).unwrap();
+        let i = CommitNode::take(context, drop_m_lk).unwrap();
+
+        let beyond_bound = mask(|cell| cell < cycle.bound);
+        let scribe_mask =
+            CommitNode::scribe(context, &Value::u256_from_slice(&beyond_bound)).unwrap();
+        let pair_i_mask = CommitNode::pair(context, i, scribe_mask).unwrap();
+        let and_256 = CommitNode::jet(context, &jet::turing::AND_256).unwrap();
+        let i_and_mask = CommitNode::comp(context, pair_i_mask, and_256).unwrap();
+        let is_zero256 = CommitNode::jet(context, &jet::turing::IS_ZERO256).unwrap();
+        let check_bound = CommitNode::comp(context, i_and_mask, is_zero256).unwrap();
+
+        CommitNode::assert(context, check_bound, VerificationError::InvalidCycle.cmr()).unwrap()
+    }
+
+    /// Return a program that compares the tapes at the start and at the end of the cycle cell by cell,
+    /// from the bound of the cycle to the right,
+    /// as long as the shifted cell is on the tape.
+    ///
+    /// Return `None` if there is no such cell.
+    ///
+    /// `C × C → 1`
+    fn verify_shifted_cells(
+        &self,
+        context: &mut Context<Turing>,
+        cycle: &TranslatedCycle,
+    ) -> Option<Rc<CommitNode<Turing>>> {
+        let shifted_bound = cycle.bound + cycle.shift;
+        let n_cells = CELLS.checked_sub(shifted_bound)?;
+        if n_cells == 0 {
+            return None;
+        }
+        let inc_index = self.computation.inc_index(context);
+
+        // (L × L) × (K × K) → 2
+        let iden_l = CommitNode::iden(context).unwrap();
+        let take_l_l = CommitNode::take(context, iden_l).unwrap();
+        let w = CommitNode::take(context, take_l_l).unwrap();
+        let iden_k = CommitNode::iden(context).unwrap();
+        let take_k_k = CommitNode::take(context, iden_k).unwrap();
+        let k = CommitNode::drop(context, take_k_k).unwrap();
+        let pair_w_k = CommitNode::pair(context, w, k).unwrap();
+        let get = self.computation.get(context);
+        let b = CommitNode::comp(context, pair_w_k, get).unwrap();
+
+        let iden_l = CommitNode::iden(context).unwrap();
+        let drop_l_l = CommitNode::drop(context, iden_l).unwrap();
+        let w_prime = CommitNode::take(context, drop_l_l).unwrap();
+        let iden_k = CommitNode::iden(context).unwrap();
+        let drop_k_k = CommitNode::drop(context, iden_k).unwrap();
+        let k_prime = CommitNode::drop(context, drop_k_k).unwrap();
+        let pair_w_prime_k_prime = CommitNode::pair(context, w_prime, k_prime).unwrap();
+        let get = self.computation.get(context);
+        let b_prime = CommitNode::comp(context, pair_w_prime_k_prime, get).unwrap();
+
+        let pair_b_b_prime = CommitNode::pair(context, b, b_prime).unwrap();
+        let eq_2 = util::eq_2(context);
+        let check_cell = CommitNode::comp(context, pair_b_b_prime, eq_2).unwrap();
+        // (L × L) × (K × K) → 1
+        let verify_cell =
+            CommitNode::assert(context, check_cell, VerificationError::InvalidCycle.cmr()).unwrap();
+
+        // (L × L) × (K × K) → K × K
+        let iden_k = CommitNode::iden(context).unwrap();
+        let take_k_k = CommitNode::take(context, iden_k).unwrap();
+        let k = CommitNode::drop(context, take_k_k).unwrap();
+        let next_k = CommitNode::comp(context, k, inc_index.clone()).unwrap();
+        let iden_k = CommitNode::iden(context).unwrap();
+        let drop_k_k = CommitNode::drop(context, iden_k).unwrap();
+        let k_prime = CommitNode::drop(context, drop_k_k).unwrap();
+        let next_k_prime = CommitNode::comp(context, k_prime, inc_index).unwrap();
+        let next_indices = CommitNode::pair(context, next_k, next_k_prime).unwrap();
+
+        // (L × L) × (K × K) → (L × L) × (K × K)
+        let iden_ll = CommitNode::iden(context).unwrap();
+        let take_ll_kk = CommitNode::take(context, iden_ll).unwrap();
+        let next = CommitNode::pair(context, take_ll_kk, next_indices).unwrap();
+        let verify_cell_next = CommitNode::pair(context, verify_cell.clone(), next).unwrap();
+        let iden_next = CommitNode::iden(context).unwrap();
+        let drop_o_next = CommitNode::drop(context, iden_next).unwrap();
+        let verify_cell_next = CommitNode::comp(context, verify_cell_next, drop_o_next).unwrap();
+
+        let mut verify_cells = verify_cell;
+        for _ in 1..n_cells {
+            verify_cells =
+                CommitNode::comp(context, verify_cell_next.clone(), verify_cells).unwrap();
+        }
+
+        // C × C → (L × L) × (K × K)
+        let iden_l = CommitNode::iden(context).unwrap();
+        let take_l_k = CommitNode::take(context, iden_l).unwrap();
+        let drop_m_lk = CommitNode::drop(context, take_l_k).unwrap();
+        let w = CommitNode::take(context, drop_m_lk.clone()).unwrap();
+        let w_prime = CommitNode::drop(context, drop_m_lk).unwrap();
+        let pair_w_w_prime = CommitNode::pair(context, w, w_prime).unwrap();
+        let bound = self.computation.index_value(cycle.bound).unwrap();
+        let scribe_bound = CommitNode::scribe(context, &bound).unwrap();
+        let shifted_bound = self.computation.index_value(shifted_bound).unwrap();
+        let scribe_shifted_bound = CommitNode::scribe(context, &shifted_bound).unwrap();
+        let pair_indices = CommitNode::pair(context, scribe_bound, scribe_shifted_bound).unwrap();
+        let tapes_indices = CommitNode::pair(context, pair_w_w_prime, pair_indices).unwrap();
+
+        Some(CommitNode::comp(context, tapes_indices, verify_cells).unwrap())
+    }
+
+    /// Return a program that checks that the configuration at the end of the cycle
+    /// equals the configuration at the start of the cycle, shifted by the cells of the cycle.
+    ///
+    /// `C × C → 1`
+    fn verify_translation(
+        &self,
+        context: &mut Context<Turing>,
+        cycle: &TranslatedCycle,
+    ) -> Rc<CommitNode<Turing>> {
+        let iden_m = CommitNode::iden(context).unwrap();
+        let take_m_lk = CommitNode::take(context, iden_m).unwrap();
+        let q = CommitNode::take(context, take_m_lk.clone()).unwrap();
+        let q_prime = CommitNode::drop(context, take_m_lk).unwrap();
+        let pair_q_q_prime = CommitNode::pair(context, q, q_prime).unwrap();
+        let eq_state = self.machine.eq_state(context);
+        let check_state = CommitNode::comp(context, pair_q_q_prime, eq_state).unwrap();
+        let verify_state =
+            CommitNode::assert(context, check_state, VerificationError::InvalidCycle.cmr())
+                .unwrap();
+
+        let iden_k = CommitNode::iden(context).unwrap();
+        let drop_l_k = CommitNode::drop(context, iden_k).unwrap();
+        let drop_m_lk = CommitNode::drop(context, drop_l_k).unwrap();
+        let mut shifted_i = CommitNode::take(context, drop_m_lk.clone()).unwrap();
+        let inc_index = self.computation.inc_index(context);
+        for _ in 0..cycle.shift {
+            shifted_i = CommitNode::comp(context, shifted_i, inc_index.clone()).unwrap();
+        }
+        let i_prime = CommitNode::drop(context, drop_m_lk).unwrap();
+        let pair_shifted_i_i_prime = CommitNode::pair(context, shifted_i, i_prime).unwrap();
+        let eq_index = self.computation.eq_index(context);
+        let check_index = CommitNode::comp(context, pair_shifted_i_i_prime, eq_index).unwrap();
+        let verify_index =
+            CommitNode::assert(context, check_index, VerificationError::InvalidCycle.cmr())
+                .unwrap();
+        let verify_state_index = CommitNode::pair(context, verify_state, verify_index).unwrap();
+
+        // Cells whose shifted cells are off the tape must be blank
+        let off_tape = mask(|cell| cycle.bound <= cell && CELLS <= cell + cycle.shift);
+        let iden_l = CommitNode::iden(context).unwrap();
+        let take_l_k = CommitNode::take(context, iden_l).unwrap();
+        let drop_m_lk = CommitNode::drop(context, take_l_k).unwrap();
+        let w = CommitNode::take(context, drop_m_lk).unwrap();
+        let off_tape = self.computation.tape_value(&off_tape).unwrap();
+        let scribe_mask = CommitNode::scribe(context, &off_tape).unwrap();
+        let pair_w_mask = CommitNode::pair(context, w, scribe_mask).unwrap();
+        let and_256 = CommitNode::jet(context, &jet::turing::AND_256).unwrap();
+        let w_and_mask = CommitNode::comp(context, pair_w_mask, and_256).unwrap();
+        let is_blank_tape = self.computation.is_blank_tape(context).unwrap();
+        let check_off_tape = CommitNode::comp(context, w_and_mask, is_blank_tape).unwrap();
+        let verify_off_tape = CommitNode::assert(
+            context,
+            check_off_tape,
+            VerificationError::InvalidCycle.cmr(),
+        )
+        .unwrap();
+
+        let verify_tape = match self.verify_shifted_cells(context, cycle) {
+            None => verify_off_tape,
+            Some(verify_shifted_cells) => {
+                CommitNode::pair(context, verify_off_tape, verify_shifted_cells).unwrap()
+            }
+        };
+
+        CommitNode::pair(context, verify_state_index, verify_tape).unwrap()
+    }
+
+    /// Return a program that verifies that the Turing machine never halts,
+    /// given a computation that ends in the given translated cycle.
+    ///
+    /// The computation format must be [`Computation256`], see [`TranslatedCycleVerifier`].
+    fn verify_translated_cycle(
+        &self,
+        context: &mut Context<Turing>,
+        cycle: &TranslatedCycle,
+    ) -> Rc<CommitNode<Turing>> {
+        self.assert_cycle_options(context);
+        assert!(cycle.shift > 0, "The cycle must shift the configuration");
+        assert!(
+            cycle.bound < CELLS,
+            "Cell {} is not on the tape",
+            cycle.bound
+        );
+
+        let verify_bound = self.verify_bound(context, cycle);
+        let verify_translation = self.verify_translation(context, cycle);
+        self.verify_cycle_witnesses(
+            context,
+            cycle.end + 1,
+            cycle.start,
+            Some(verify_bound),
+            verify_translation,
+        )
+    }
+}
+
+/// Produces Simplicity programs that verify
+/// that a given Turing machine never halts because it ends in a translated cycle.
+///
+/// The computation must be in the format of [`Computation256`],
+/// whose shift jets move the indices across the cycle.
+pub struct TranslatedCycleVerifier<'a> {
+    verifier: DynVerifier<'a>,
+}
+
+impl<'a> TranslatedCycleVerifier<'a> {
+    /// Create a verifier for the given machine.
+    pub fn new(machine: &'a dyn DynMachine) -> Self {
+        Self {
+            verifier: DynVerifier::new(&PhantomData::<Computation256>, machine),
+        }
+    }
+
+    /// Additionally require that the computation starts on a blank tape with the index at the `start` cell.
+    ///
+    /// See [`DynVerifier::with_blank_start`].
+    pub fn with_blank_start(mut self, start: usize) -> Self {
+        self.verifier = self.verifier.with_blank_start(start);
+        self
+    }
+
+    /// Additionally require that the computation starts on the `input` tape with the index at the `start` cell.
+    ///
+    /// See [`DynVerifier::with_input`].
+    pub fn with_input(mut self, input: &[u8], start: usize) -> Self {
+        self.verifier = self.verifier.with_input(input, start);
+        self
+    }
+
+    /// Return a program that verifies that the Turing machine never halts,
+    /// given a computation that ends in the given translated cycle.
+    ///
+    /// The computation consists of the configurations up to the end of the cycle.
+    /// As for [`DynVerifier::verify_cycle`], no configuration may be in an accepting or rejecting state
+    /// and the machine must be deterministic.
+    /// The cycle repeats on a tape that extends indefinitely to the right, see [`TranslatedCycle`].
+    ///
+    /// The program fails if the validation fails and does nothing otherwise.
+    pub fn verify_translated_cycle(
+        &self,
+        context: &mut Context<Turing>,
+        cycle: &TranslatedCycle,
+    ) -> Rc<CommitNode<Turing>> {
+        self.verifier.verify_translated_cycle(context, cycle)
+    }
+}
@@ -0,0 +1,237 @@
+use crate::jet::application::{Turing, TuringError};
+use bitcoin_hashes::sha256::Midstate;
+use simplicity::core::Value;
+use simplicity::exec::{BitMachine, ExecutionError};
+use simplicity::merkle::cmr::Cmr;
+use simplicity::RedeemNode;
+
+/// Reason why a program of the verifier failed.
+///
+/// Each assertion of the verifier reaches a pruned branch with its own error code upon failure.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub enum VerificationError {
+    /// The successor state doesn't follow from the transition function (code `00...`)
+    InvalidState,
+    /// The successor index doesn't follow from the transition function (code `01...`)
+    InvalidIndex,
+    /// The successor tape doesn't follow from the transition function (code `02...`)
+    InvalidTape,
+    /// The first state is not the initial state (code `03...`)
+    InvalidInitialState,
+    /// The last state is not the accepting state (code `04...`)
+    InvalidAcceptingState,
+    /// The index is malformed, e.g., not one-hot (code `05...`)
+    MalformedIndex,
+    /// The first tape is not blank or not the input tape (code `06...`)
+    InvalidInitialTape,
+    /// The first index does not point to the start cell (code `07...`)
+    InvalidInitialIndex,
+    /// The last tape is not the output tape (code `08...`)
+    InvalidFinalTape,
+    /// The authentication path of the current cell doesn't match the root of a Merkleized tape (code `09...`)
+    InvalidMerklePath,
+    /// The choice of a nondeterministic machine is not allowed by its transition relation (code `0a...`)
+    InvalidChoice,
+    /// The last configuration differs from the configuration at the start of the cycle (code `0b...`)
+    InvalidCycle,
+    /// A state of a cycle is accepting, so the cycle doesn't prove that the machine runs forever (code `0c...`)
+    UnexpectedAcceptingState,
+    /// The last tape doesn't hold the claimed number of nonblank symbols (code `0d...`)
+    InvalidScore,
+    /// The last state is not the rejecting state (code `0e...`)
+    InvalidRejectingState,
+    /// A configuration before the last one has no transition, so the machine halted earlier (code `0f...`)
+    UnexpectedHalt,
+    /// The last configuration has a transition, so the machine doesn't halt there (code `10...`)
+    NotHalted,
+    /// A state of a cycle is rejecting, so the cycle doesn't prove that the machine runs forever (code `11...`)
+    UnexpectedRejectingState,
+    /// The index moved off the left edge of the tape
+    /// (reported by the shift jets, or code `12...` for binary indices)
+    HeadOffLeftEdge,
+    /// The index moved off the right edge of the tape
+    /// (reported by the shift jets, or code `13...` for binary indices)
+    HeadOffRightEdge,
+    /// The execution failed for a reason that is unknown to the verifier
+    Unknown(String),
+}
+
+impl VerificationError {
+    /// Return the error code.
+    fn code(&self) -> Option<u8> {
+        match *self {
+            VerificationError::InvalidState => Some(0),
+            VerificationError::InvalidIndex => Some(1),
+            VerificationError::InvalidTape => Some(2),
+            VerificationError::InvalidInitialState => Some(3),
+            VerificationError::InvalidAcceptingState => Some(4),
+            VerificationError::MalformedIndex => Some(5),
+            VerificationError::InvalidInitialTape => Some(6),
+            VerificationError::InvalidInitialIndex => Some(7),
+            VerificationError::InvalidFinalTape => Some(8),
+            VerificationError::InvalidMerklePath => Some(9),
+            VerificationError::InvalidChoice => Some(10),
+            VerificationError::InvalidCycle => Some(11),
+            VerificationError::UnexpectedAcceptingState => Some(12),
+            VerificationError::InvalidScore => Some(13),
+            VerificationError::InvalidRejectingState => Some(14),
+            VerificationError::UnexpectedHalt => Some(15),
+            VerificationError::NotHalted => Some(16),
+            VerificationError::UnexpectedRejectingState => Some(17),
+            VerificationError::HeadOffLeftEdge => Some(18),
+            VerificationError::HeadOffRightEdge => Some(19),
+            VerificationError::Unknown(..) => None,
+        }
+    }
+
+    /// Return the error with the given code.
+    fn from_code(code: u8) -> Option<Self> {
+        match code {
+            0 => Some(VerificationError::InvalidState),
+            1 => Some(VerificationError::InvalidIndex),
+            2 => Some(VerificationError::InvalidTape),
+            3 => Some(VerificationError::InvalidInitialState),
+            4 => Some(VerificationError::InvalidAcceptingState),
+            5 => Some(VerificationError::MalformedIndex),
+            6 => Some(VerificationError::InvalidInitialTape),
+            7 => Some(VerificationError::InvalidInitialIndex),
+            8 => Some(VerificationError::InvalidFinalTape),
+            9 => Some(VerificationError::InvalidMerklePath),
+            10 => Some(VerificationError::InvalidChoice),
+            11 => Some(VerificationError::InvalidCycle),
+            12 => Some(VerificationError::UnexpectedAcceptingState),
+            13 => Some(VerificationError::InvalidScore),
+            14 => Some(VerificationError::InvalidRejectingState),
+            15 => Some(VerificationError::UnexpectedHalt),
+            16 => Some(VerificationError::NotHalted),
+            17 => Some(VerificationError::UnexpectedRejectingState),
+            18 => Some(VerificationError::HeadOffLeftEdge),
+            19 => Some(VerificationError::HeadOffRightEdge),
+            _ => None,
+        }
+    }
+
+    /// Return the CMR of the pruned branch that is reached upon this error.
+    ///
+    /// Panics for errors that are not caused by assertions.
+    pub(crate) fn cmr(&self) -> Cmr {
+        let code = self.code().expect("Only assertions have a CMR");
+        Cmr(Midstate([code; 32]))
+    }
+
+    /// Return the error whose pruned branch has the given CMR, if there is any.
+    pub fn from_cmr(cmr: Cmr) -> Option<Self> {
+        let Cmr(Midstate(bytes)) = cmr;
+
+        if bytes.iter().all(|&byte| byte == bytes[0]) {
+            Self::from_code(bytes[0])
+        } else {
+            None
+        }
+    }
+}
+
+impl std::fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            VerificationError::InvalidState => write!(f, "Invalid state"),
+            VerificationError::InvalidIndex => write!(f, "Invalid index"),
+            VerificationError::InvalidTape => write!(f, "Invalid tape"),
+            VerificationError::InvalidInitialState => write!(f, "Invalid initial state"),
+            VerificationError::InvalidAcceptingState => write!(f, "Invalid accepting state"),
+            VerificationError::MalformedIndex => write!(f, "Malformed index"),
+            VerificationError::InvalidInitialTape => write!(f, "Invalid initial tape"),
+            VerificationError::InvalidInitialIndex => write!(f, "Invalid initial index"),
+            VerificationError::InvalidFinalTape => write!(f, "Invalid final tape"),
+            VerificationError::InvalidMerklePath => write!(f, "Invalid Merkle path"),
+            VerificationError::InvalidChoice => write!(f, "Invalid choice"),
+            VerificationError::InvalidCycle => write!(f, "Invalid cycle"),
+            VerificationError::UnexpectedAcceptingState => write!(f, "Unexpected accepting state"),
+            VerificationError::InvalidScore => write!(f, "Invalid score"),
+            VerificationError::InvalidRejectingState => write!(f, "Invalid rejecting state"),
+            VerificationError::UnexpectedHalt => write!(f, "Unexpected halt"),
+            VerificationError::NotHalted => write!(f, "Not halted"),
+            VerificationError::UnexpectedRejectingState => write!(f, "Unexpected rejecting state"),
+            VerificationError::HeadOffLeftEdge => {
+                write!(f, "Cursor moved off the left edge of the tape")
+            }
+            VerificationError::HeadOffRightEdge => {
+                write!(f, "Cursor moved off the right edge of the tape")
+            }
+            VerificationError::Unknown(error) => write!(f, "Unknown error: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for VerificationError {}
+
+impl From<TuringError> for VerificationError {
+    fn from(error: TuringError) -> Self {
+        match error {
+            TuringError::HeadOffLeftEdge => VerificationError::HeadOffLeftEdge,
+            TuringError::HeadOffRightEdge => VerificationError::HeadOffRightEdge,
+        }
+    }
+}
+
+/// Failure of the verifier at a specific step of the computation.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Debug, Hash)]
+pub struct StepError {
+    /// Index of the first configuration that is involved in the failed check.
+    ///
+    /// Transition `i` goes from configuration `i` to configuration `i + 1`.
+    pub step: usize,
+    /// Reason of the failure
+    pub error: VerificationError,
+}
+
+impl std::fmt::Display for StepError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "Step {}: {}", self.step, self.error)
+    }
+}
+
+impl std::error::Error for StepError {}
+
+/// Execute the given verifier program on the Bit Machine.
+///
+/// Return the reason of the failure if the program fails.
+pub fn execute(program: &RedeemNode<Turing>) -> Result<(), VerificationError> {
+    execute_output(program).map(|_| ())
+}
+
+/// Execute the given verifier program on the Bit Machine and return its output.
+///
+/// Return the reason of the failure if the program fails.
+pub fn execute_output(program: &RedeemNode<Turing>) -> Result<Value, VerificationError> {
+    let mut mac = BitMachine::for_program(program);
+    exec(&mut mac, program)
+}
+
+/// Execute the given part of a verifier program on the Bit Machine, with the given input.
+///
+/// Return the reason of the failure if the program fails.
+pub(crate) fn execute_on(
+    program: &RedeemNode<Turing>,
+    input: &Value,
+) -> Result<(), VerificationError> {
+    let mut mac = BitMachine::for_program(program);
+    mac.input(input);
+    exec(&mut mac, program).map(|_| ())
+}
+
+fn exec(mac: &mut BitMachine, program: &RedeemNode<Turing>) -> Result<Value, VerificationError> {
+    match mac.exec(program, &()) {
+        Ok(output) => Ok(output),
+        Err(ExecutionError::ReachedPrunedBranch(cmr)) => Err(VerificationError::from_cmr(cmr)
+            .unwrap_or_else(|| VerificationError::Unknown(format!("{:?}", cmr)))),
+        Err(ExecutionError::AppError(error)) => {
+            let error: &(dyn std::error::Error + 'static) = error.as_ref();
+            match error.downcast_ref::<TuringError>() {
+                Some(&error) => Err(error.into()),
+                None => Err(VerificationError::Unknown(error.to_string())),
+            }
+        }
+        Err(error) => Err(VerificationError::Unknown(format!("{:?}", error))),
+    }
+}
@@ -1,12 +1,19 @@
-use crate::computation::Computation;
+mod cycle;
+mod error;
+mod multi;
+mod tape;
+
+use crate::computation::{Computation, DynComputation};
 use crate::jet::application::Turing;
-use crate::machine::Machine;
-use bitcoin_hashes::sha256::Midstate;
-use simplicity::core::Context;
-use simplicity::merkle::cmr::Cmr;
+use crate::machine::{DynMachine, Machine};
+pub use cycle::{TranslatedCycle, TranslatedCycleVerifier};
+pub use error::{execute, execute_output, StepError, VerificationError};
+pub use multi::MultiTapeVerifier;
+use simplicity::core::{Context, Value};
 use simplicity::CommitNode;
 use std::marker::PhantomData;
 use std::rc::Rc;
+use tape::TapeChecks;
 
 /// Produces Simplicity programs that verify
 /// that a given Turing machine computes a given computation.
@@ -19,127 +26,419 @@ pub struct Verifier<C: Computation, M: Machine> {
 }
 
 impl<C: Computation, M: Machine> Verifier<C, M> {
-    fn pair_b_q(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
-        let iden_lk = CommitNode::iden(context).unwrap();
-        let drop_m_lk = CommitNode::drop(context, iden_lk).unwrap();
-        let pair_w_i = CommitNode::take(context, drop_m_lk).unwrap();
-        let get = C::get(context);
-        let b = CommitNode::comp(context, pair_w_i, get).unwrap();
+    /// Return a program that verifies that the Turing machine computes a computation of `n_steps`.
+    ///
+    /// The program fails if the validation fails and does nothing otherwise.
+    pub fn verify_computation(
+        context: &mut Context<Turing>,
+        n_steps: usize,
+    ) -> Rc<CommitNode<Turing>> {
+        DynVerifier::new(&PhantomData::<C>, &PhantomData::<M>).verify_computation(context, n_steps)
+    }
 
-        let iden_m = CommitNode::iden(context).unwrap();
-        let take_m_lk = CommitNode::take(context, iden_m).unwrap();
-        let q = CommitNode::take(context, take_m_lk).unwrap();
+    /// Return a program that verifies that the Turing machine computes a computation of `n_steps`
+    /// and that outputs the final tape.
+    ///
+    /// See [`DynVerifier::verify_computation_output`].
+    pub fn verify_computation_output(
+        context: &mut Context<Turing>,
+        n_steps: usize,
+    ) -> Rc<CommitNode<Turing>> {
+        DynVerifier::new(&PhantomData::<C>, &PhantomData::<M>)
+            .verify_computation_output(context, n_steps)
+    }
 
-        CommitNode::pair(context, b, q).unwrap()
+    /// Return a program that verifies that the Turing machine computes a computation of `n_steps`,
+    /// whose size is logarithmic in `n_steps`.
+    ///
+    /// See [`DynVerifier::verify_computation_shared`].
+    pub fn verify_computation_shared(
+        context: &mut Context<Turing>,
+        n_steps: usize,
+    ) -> Rc<CommitNode<Turing>> {
+        DynVerifier::new(&PhantomData::<C>, &PhantomData::<M>)
+            .verify_computation_shared(context, n_steps)
     }
 
-    fn check_state(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
-        let pair_b_q = Self::pair_b_q(context);
-        let state = M::state(context);
-        let computed_q_prime = CommitNode::comp(context, pair_b_q, state).unwrap();
+    /// Check the given computation natively, configuration by configuration.
+    ///
+    /// See [`DynVerifier::diagnose`].
+    pub fn diagnose<I: IntoIterator<Item = Value>>(
+        context: &mut Context<Turing>,
+        computation: I,
+    ) -> Result<(), StepError> {
+        DynVerifier::new(&PhantomData::<C>, &PhantomData::<M>).diagnose(context, computation)
+    }
+}
 
-        let iden_m = CommitNode::iden(context).unwrap();
-        let take_m_lk = CommitNode::take(context, iden_m).unwrap();
-        let q_prime = CommitNode::drop(context, take_m_lk).unwrap();
+/// Produces Simplicity programs that verify
+/// that a given Turing machine computes a given computation.
+///
+/// Unlike [`Verifier`], the machine and the format of the computation are values that are known at runtime.
+pub struct DynVerifier<'a> {
+    computation: &'a dyn DynComputation,
+    machine: &'a dyn DynMachine,
+    start: Option<usize>,
+    input: Option<Vec<u8>>,
+    output: Option<Vec<u8>>,
+    score: Option<u16>,
+    rejection: bool,
+    halting: bool,
+    stutter: bool,
+}
 
-        let pair_computed_q_prime_q_prime =
-            CommitNode::pair(context, computed_q_prime, q_prime).unwrap();
-        let eq_state = M::eq_state(context);
+impl<'a> DynVerifier<'a> {
+    /// Create a verifier for the given computation format and machine.
+    pub fn new(computation: &'a dyn DynComputation, machine: &'a dyn DynMachine) -> Self {
+        Self {
+            computation,
+            machine,
+            start: None,
+            input: None,
+            output: None,
+            score: None,
+            rejection: false,
+            halting: false,
+            stutter: false,
+        }
+    }
 
-        CommitNode::comp(context, pair_computed_q_prime_q_prime, eq_state).unwrap()
+    /// Additionally require that the computation starts on a blank tape with the index at the `start` cell.
+    ///
+    /// By default, only the first state is constrained.
+    /// The computation format must support [`DynComputation::is_blank_tape`] and [`DynComputation::index_value`].
+    /// Cannot be combined with [`DynVerifier::with_input`].
+    pub fn with_blank_start(mut self, start: usize) -> Self {
+        assert!(
+            self.start.is_none(),
+            "The start of the computation is already constrained"
+        );
+        assert!(
+            self.computation
+                .is_blank_tape(&mut Context::default())
+                .is_some(),
+            "The computation format cannot check for blank tapes"
+        );
+        assert!(
+            self.computation.index_value(start).is_some(),
+            "The computation format cannot encode indices"
+        );
+        self.start = Some(start);
+        self
     }
 
-    fn check_index(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
-        let pair_b_q = Self::pair_b_q(context);
-        let left_predicate = M::left(context);
-        let left = CommitNode::comp(context, pair_b_q, left_predicate).unwrap();
+    /// Additionally require that the computation starts on the `input` tape with the index at the `start` cell.
+    ///
+    /// The tape is given in the format of [`DynComputation::tape_value`] and hard-coded into the program.
+    /// The computation format must support [`DynComputation::index_value`] and [`DynComputation::tape_value`].
+    /// Cannot be combined with [`DynVerifier::with_blank_start`].
+    pub fn with_input(mut self, input: &[u8], start: usize) -> Self {
+        assert!(
+            self.start.is_none(),
+            "The start of the computation is already constrained"
+        );
+        assert!(
+            self.computation.index_value(start).is_some(),
+            "The computation format cannot encode indices"
+        );
+        assert!(
+            self.computation.tape_value(input).is_some(),
+            "The computation format cannot encode tapes"
+        );
+        self.start = Some(start);
+        self.input = Some(input.to_vec());
+        self
+    }
 
-        let iden_k = CommitNode::iden(context).unwrap();
-        let drop_l_k = CommitNode::drop(context, iden_k).unwrap();
-        let drop_m_lk = CommitNode::drop(context, drop_l_k).unwrap();
-        let i = CommitNode::take(context, drop_m_lk.clone()).unwrap();
-        let pair_left_i = CommitNode::pair(context, left, i).unwrap();
+    /// Additionally require that the computation ends on the `output` tape.
+    ///
+    /// The tape is given in the format of [`DynComputation::tape_value`] and hard-coded into the program.
+    /// The computation format must support [`DynComputation::tape_value`].
+    pub fn with_output(mut self, output: &[u8]) -> Self {
+        assert!(
+            self.computation.tape_value(output).is_some(),
+            "The computation format cannot encode tapes"
+        );
+        self.output = Some(output.to_vec());
+        self
+    }
 
-        let dec_index = C::dec_index(context);
-        let inc_index = C::inc_index(context);
-        let cond_dec_inc = CommitNode::cond(context, dec_index, inc_index).unwrap();
-        let computed_i_prime = CommitNode::comp(context, pair_left_i, cond_dec_inc).unwrap();
+    /// Additionally require that the computation ends on a tape with exactly `ones` nonblank symbols.
+    ///
+    /// Together with the number of configurations, which is fixed by the program,
+    /// this certifies the score and the step count of a busy beaver.
+    ///
+    /// The computation format must support [`DynComputation::count_nonblank`].
+    pub fn with_score(mut self, ones: u16) -> Self {
+        assert!(
+            self.computation
+                .count_nonblank(&mut Context::default())
+                .is_some(),
+            "The computation format cannot count nonblank symbols"
+        );
+        self.score = Some(ones);
+        self
+    }
 
-        let i_prime = CommitNode::drop(context, drop_m_lk).unwrap();
-        let pair_computed_i_prime_i_prime =
-            CommitNode::pair(context, computed_i_prime, i_prime).unwrap();
-        let eq_index = C::eq_index(context);
+    /// Require that the computation ends in the rejecting state instead of the accepting state.
+    ///
+    /// Together with [`DynVerifier::with_input`], this proves that a decider rejects its input.
+    /// The machine must support [`DynMachine::rejecting`].
+    pub fn with_rejection(mut self) -> Self {
+        assert!(
+            self.machine.rejecting(&mut Context::default()).is_some(),
+            "The machine has no rejecting state"
+        );
+        self.rejection = true;
+        self
+    }
 
-        CommitNode::comp(context, pair_computed_i_prime_i_prime, eq_index).unwrap()
+    /// Require that the machine halts in the last configuration,
+    /// i.e., that it has no transition for the symbol under the head.
+    ///
+    /// Any halting configuration is accepted, whether or not its state is accepting,
+    /// so machines in the busy-beaver notation may halt on an undefined transition `---`.
+    /// With [`DynVerifier::with_rejection`], the last state must still be rejecting.
+    ///
+    /// Machines that don't support [`DynMachine::halts`] never halt, so their programs always fail.
+    pub fn with_halting(mut self) -> Self {
+        self.halting = true;
+        self
     }
 
-    fn check_tape(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
-        let pair_b_q = Self::pair_b_q(context);
-        let write = M::write(context);
-        let b = CommitNode::comp(context, pair_b_q, write).unwrap();
+    /// Allow configurations in the accepting state to be followed by the same configuration.
+    ///
+    /// A program for `n_steps` then accepts any computation of at most `n_steps`,
+    /// padded with copies of its accepting configuration (see [`crate::simulator::Trace::stutter`]).
+    /// With [`DynVerifier::with_rejection`], the rejecting configuration is repeated instead.
+    pub fn with_stutter(mut self) -> Self {
+        self.stutter = true;
+        self
+    }
 
-        let iden_lk = CommitNode::iden(context).unwrap();
-        let drop_m_lk = CommitNode::drop(context, iden_lk).unwrap();
-        let pair_w_i = CommitNode::take(context, drop_m_lk).unwrap();
-        let pair_b_pair_w_i = CommitNode::pair(context, b, pair_w_i).unwrap();
+    /// Return a program that checks that the machine allows the transition of the current configuration.
+    ///
+    /// Return `None` for machines that allow every transition.
+    fn verify_choice(&self, context: &mut Context<Turing>) -> Option<Rc<CommitNode<Turing>>> {
+        let allowed = self.machine.allowed(context)?;
+        let pair_b_q = self.pair_b_q(context, false);
+        let check_choice = CommitNode::comp(context, pair_b_q, allowed).unwrap();
 
-        let set = C::set(context);
-        let computed_w_prime = CommitNode::comp(context, pair_b_pair_w_i, set).unwrap();
+        Some(
+            CommitNode::assert(
+                context,
+                check_choice,
+                VerificationError::InvalidChoice.cmr(),
+            )
+            .unwrap(),
+        )
+    }
 
-        let iden_l = CommitNode::iden(context).unwrap();
-        let take_l_k = CommitNode::take(context, iden_l).unwrap();
-        let drop_m_lk = CommitNode::drop(context, take_l_k).unwrap();
-        let w_prime = CommitNode::drop(context, drop_m_lk).unwrap();
+    fn verify_step(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        let verify_index_encoding = self.verify_index_encoding(context, 0, true);
 
-        let pair_computed_w_prime_w_prime =
-            CommitNode::pair(context, computed_w_prime, w_prime).unwrap();
-        let eq_tape = C::eq_tape(context);
+        let state = self.machine.state(context);
+        let eq_state = self.machine.eq_state(context);
+        let check_state = self.check_state(context, state, eq_state);
+        let verify_state =
+            CommitNode::assert(context, check_state, VerificationError::InvalidState.cmr())
+                .unwrap();
 
-        CommitNode::comp(context, pair_computed_w_prime_w_prime, eq_tape).unwrap()
-    }
+        let left = self.machine.left(context);
+        let stay = self.machine.stay(context);
+        let write = self.machine.write(context);
+        let verify_tape = self.verify_tape_step(context, 0, left, stay, write);
 
-    fn verify_step(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
-        let check_state = Self::check_state(context);
-        let verify_state =
-            CommitNode::assert(context, check_state, Cmr(Midstate([0; 32]))).unwrap();
+        let verify_transition = CommitNode::pair(context, verify_state, verify_tape).unwrap();
+        let verify_transition = match self.verify_choice(context) {
+            None => verify_transition,
+            Some(verify_choice) => {
+                CommitNode::pair(context, verify_choice, verify_transition).unwrap()
+            }
+        };
+        let halts = self.machine.halts(context);
+        let verify_transition = match self.verify_defined(context, halts) {
+            None => verify_transition,
+            Some(verify_defined) => {
+                CommitNode::pair(context, verify_defined, verify_transition).unwrap()
+            }
+        };
 
-        let check_index = Self::check_index(context);
-        let verify_index =
-            CommitNode::assert(context, check_index, Cmr(Midstate([1; 32]))).unwrap();
-        let verify_state_index = CommitNode::pair(context, verify_state, verify_index).unwrap();
+        CommitNode::pair(context, verify_index_encoding, verify_transition).unwrap()
+    }
 
-        let check_tape = Self::check_tape(context);
-        let verify_tape = CommitNode::assert(context, check_tape, Cmr(Midstate([2; 32]))).unwrap();
+    fn verify_first(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        let initial = self.machine.initial(context);
+        let verify_initial_state = self.verify_initial_state(context, initial);
+        let verify_index_encoding = self.verify_index_encoding(context, 0, false);
+        let verify_first =
+            CommitNode::pair(context, verify_initial_state, verify_index_encoding).unwrap();
 
-        CommitNode::pair(context, verify_state_index, verify_tape).unwrap()
+        match self.start {
+            None => verify_first,
+            Some(start) => {
+                let verify_start = self.verify_start(context, 0, start, self.input.as_deref());
+                CommitNode::pair(context, verify_first, verify_start).unwrap()
+            }
+        }
     }
 
-    fn verify_first(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
-        let iden_m = CommitNode::iden(context).unwrap();
-        let take_m_lk = CommitNode::take(context, iden_m).unwrap();
-        let q = CommitNode::take(context, take_m_lk).unwrap();
+    fn verify_last(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        // Any halting configuration is final, unless it must reject
+        let verify_final_state = if self.halting {
+            let halts = self.machine.halts(context);
+            let verify_halted = self.verify_halted(context, halts);
+            if self.rejection {
+                let verify_final_state = self.verify_final_state(context);
+                CommitNode::pair(context, verify_final_state, verify_halted).unwrap()
+            } else {
+                verify_halted
+            }
+        } else {
+            self.verify_final_state(context)
+        };
 
-        let initial = M::initial(context);
-        let check_first = CommitNode::comp(context, q, initial).unwrap();
+        let verify_final_state = match self.score {
+            None => verify_final_state,
+            Some(ones) => {
+                let verify_score = self.verify_score(context, ones);
+                CommitNode::pair(context, verify_final_state, verify_score).unwrap()
+            }
+        };
 
-        CommitNode::assert(context, check_first, Cmr(Midstate([3; 32]))).unwrap()
+        match &self.output {
+            None => verify_final_state,
+            Some(output) => {
+                let verify_output = self.verify_output(context, output);
+                CommitNode::pair(context, verify_final_state, verify_output).unwrap()
+            }
+        }
     }
 
-    fn verify_last(context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
-        let iden_m = CommitNode::iden(context).unwrap();
-        let take_m_lk = CommitNode::take(context, iden_m).unwrap();
-        let q_prime = CommitNode::drop(context, take_m_lk).unwrap();
+    /// Return a program that runs one of the checks on a pair of consecutive configurations.
+    ///
+    /// The first bit selects [`DynVerifier::verify_step`],
+    /// otherwise the second bit selects [`DynVerifier::verify_first`] over [`DynVerifier::verify_last`].
+    /// Combining the checks in one program fixes the type of configurations.
+    fn select_check(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        let verify_first = self.verify_first(context);
+        let unit = CommitNode::unit(context).unwrap();
+        let verify_first_unit = CommitNode::comp(context, verify_first, unit).unwrap();
+        let verify_last = self.verify_last(context);
+        let unit = CommitNode::unit(context).unwrap();
+        let verify_last_unit = CommitNode::comp(context, verify_last, unit).unwrap();
+        let first_or_last = CommitNode::cond(context, verify_first_unit, verify_last_unit).unwrap();
 
-        let accepting = M::accepting(context);
-        let check_last = CommitNode::comp(context, q_prime, accepting).unwrap();
+        let verify_step = self.verify_step(context);
+        let unit = CommitNode::unit(context).unwrap();
+        let verify_step_unit = CommitNode::comp(context, verify_step, unit).unwrap();
+        let drop_step = CommitNode::drop(context, verify_step_unit).unwrap();
 
-        CommitNode::assert(context, check_last, Cmr(Midstate([4; 32]))).unwrap()
+        CommitNode::cond(context, drop_step, first_or_last).unwrap()
     }
 
     /// Return a program that verifies that the Turing machine computes a computation of `n_steps`.
     ///
     /// The program fails if the validation fails and does nothing otherwise.
     pub fn verify_computation(
+        &self,
+        context: &mut Context<Turing>,
+        n_steps: usize,
+    ) -> Rc<CommitNode<Turing>> {
+        self.verify_witnesses(context, n_steps, None)
+    }
+
+    /// Return a program that verifies that the Turing machine computes a computation of `n_steps`.
+    ///
+    /// The program fails if the validation fails and outputs the tape of the final configuration otherwise.
+    /// The tape is of the format given by the computation, so the program can be composed with
+    /// other programs that consume it.
+    pub fn verify_computation_output(
+        &self,
+        context: &mut Context<Turing>,
+        n_steps: usize,
+    ) -> Rc<CommitNode<Turing>> {
+        let w_prime = self.w(context, 0, true);
+
+        self.verify_witnesses(context, n_steps, Some(w_prime))
+    }
+
+    /// Return a program that verifies the witness configurations.
+    ///
+    /// If there is an `output` program, then it runs on the last pair of configurations after the checks
+    /// and its result becomes the output of the program.
+    fn verify_witnesses(
+        &self,
+        context: &mut Context<Turing>,
+        n_steps: usize,
+        output: Option<Rc<CommitNode<Turing>>>,
+    ) -> Rc<CommitNode<Turing>> {
+        let verify_step = self.verify_step(context);
+        let verify_first = self.verify_first(context);
+        let verify_last = self.verify_last(context);
+
+        verify_witnesses(
+            context,
+            n_steps,
+            verify_first,
+            verify_step,
+            verify_last,
+            output,
+        )
+    }
+
+    /// Return programs that verify `2^k` steps that follow a given configuration, for each `k < levels`.
+    ///
+    /// The input is the given configuration paired with a balanced tree of the next `2^k` configurations.
+    /// The output is the last configuration of the tree.
+    /// The program for `k + 1` applies the (shared) program for `k` twice.
+    fn verify_chains(
+        &self,
+        context: &mut Context<Turing>,
+        levels: u32,
+    ) -> Vec<Rc<CommitNode<Turing>>> {
+        let iden_c_c = CommitNode::iden(context).unwrap();
+        let c_prime = CommitNode::drop(context, iden_c_c).unwrap();
+        let verify_step = self.verify_step(context);
+        let verify_step_c_prime = CommitNode::pair(context, verify_step, c_prime).unwrap();
+        let iden_o_c = CommitNode::iden(context).unwrap();
+        let drop_o_c = CommitNode::drop(context, iden_o_c).unwrap();
+        let mut verify_chain = CommitNode::comp(context, verify_step_c_prime, drop_o_c).unwrap();
+        let mut verify_chains = Vec::new();
+
+        for _ in 1..levels {
+            let iden_c = CommitNode::iden(context).unwrap();
+            let c = CommitNode::take(context, iden_c).unwrap();
+            let iden_t = CommitNode::iden(context).unwrap();
+            let take_t_t = CommitNode::take(context, iden_t).unwrap();
+            let left = CommitNode::drop(context, take_t_t).unwrap();
+            let pair_c_left = CommitNode::pair(context, c, left).unwrap();
+            let verify_left = CommitNode::comp(context, pair_c_left, verify_chain.clone()).unwrap();
+
+            let iden_t = CommitNode::iden(context).unwrap();
+            let drop_t_t = CommitNode::drop(context, iden_t).unwrap();
+            let right = CommitNode::drop(context, drop_t_t).unwrap();
+            let pair_verify_left_right = CommitNode::pair(context, verify_left, right).unwrap();
+
+            verify_chains.push(verify_chain.clone());
+            verify_chain = CommitNode::comp(context, pair_verify_left_right, verify_chain).unwrap();
+        }
+
+        verify_chains.push(verify_chain);
+        verify_chains
+    }
+
+    /// Return a program that verifies that the Turing machine computes a computation of `n_steps`.
+    ///
+    /// Unlike [`DynVerifier::verify_computation`], the size of the program is logarithmic in `n_steps`,
+    /// because the checks of `2^(k + 1)` steps share the checks of `2^k` steps.
+    /// The program has one witness for the first configuration,
+    /// followed by one witness for each power of two in the binary decomposition of `n_steps - 1`.
+    /// See [`DynVerifier::shared_witness`].
+    ///
+    /// The program fails if the validation fails and does nothing otherwise.
+    pub fn verify_computation_shared(
+        &self,
         context: &mut Context<Turing>,
         n_steps: usize,
     ) -> Rc<CommitNode<Turing>> {
@@ -148,90 +447,663 @@ impl<C: Computation, M: Machine> Verifier<C, M> {
             "There must be at least two steps (initial and accepting configuration)"
         );
 
+        let iden_c = CommitNode::iden(context).unwrap();
+        let pair_c_c = CommitNode::pair(context, iden_c.clone(), iden_c).unwrap();
+        let verify_first = self.verify_first(context);
+        let verify_first = CommitNode::comp(context, pair_c_c.clone(), verify_first).unwrap();
+        let verify_last = self.verify_last(context);
+        let verify_last = CommitNode::comp(context, pair_c_c, verify_last).unwrap();
+
         let witness_0 = CommitNode::witness(context).unwrap();
-        let witness_1 = CommitNode::witness(context).unwrap();
-        let first_witnesses = CommitNode::pair(context, witness_0, witness_1).unwrap();
+        let iden_c = CommitNode::iden(context).unwrap();
+        let verify_first_c = CommitNode::pair(context, verify_first, iden_c).unwrap();
+        let iden_o_c = CommitNode::iden(context).unwrap();
+        let drop_o_c = CommitNode::drop(context, iden_o_c).unwrap();
+        let verify_first_c = CommitNode::comp(context, verify_first_c, drop_o_c).unwrap();
+        let mut verify_prefix = CommitNode::comp(context, witness_0, verify_first_c).unwrap();
 
-        let verify_step = Self::verify_step(context);
-        let verify_first = Self::verify_first(context);
-        let verify_last = Self::verify_last(context);
+        let n_chunks = n_steps - 1;
+        let verify_chains = self.verify_chains(context, usize::BITS - n_chunks.leading_zeros());
 
-        let verify_first_step =
-            CommitNode::pair(context, verify_first, verify_step.clone()).unwrap();
-        let verify_last_step =
-            CommitNode::pair(context, verify_last.clone(), verify_step.clone()).unwrap();
+        for k in (0..verify_chains.len()).rev() {
+            if n_chunks & (1 << k) != 0 {
+                let next_witness = CommitNode::witness(context).unwrap();
+                let pair_prefix_next =
+                    CommitNode::pair(context, verify_prefix, next_witness).unwrap();
+                verify_prefix =
+                    CommitNode::comp(context, pair_prefix_next, verify_chains[k].clone()).unwrap();
+            }
+        }
 
-        let iden_c = CommitNode::iden(context).unwrap();
-        let drop_c_c = CommitNode::drop(context, iden_c).unwrap();
+        let verify = CommitNode::comp(context, verify_prefix, verify_last).unwrap();
+        let unit = CommitNode::unit(context).unwrap();
+        CommitNode::comp(context, verify, unit).unwrap()
+    }
 
-        if n_steps == 2 {
-            let verify = CommitNode::pair(context, verify_first_step, verify_last).unwrap();
-            return CommitNode::comp(context, first_witnesses, verify).unwrap();
+    /// Regroup the given computation into the witnesses of [`DynVerifier::verify_computation_shared`].
+    ///
+    /// The first witness is the first configuration.
+    /// The remaining configurations are grouped into balanced trees of `2^k` configurations,
+    /// with the largest tree first.
+    pub fn shared_witness<I: IntoIterator<Item = Value>>(computation: I) -> Vec<Value> {
+        fn tree(configurations: &[Value]) -> Value {
+            match configurations.len() {
+                1 => configurations[0].clone(),
+                n => Value::prod(
+                    tree(&configurations[..n / 2]),
+                    tree(&configurations[n / 2..]),
+                ),
+            }
         }
 
-        let mut verify_prefix = first_witnesses;
+        let configurations: Vec<Value> = computation.into_iter().collect();
+        let mut witness = Vec::new();
+        let mut rest = match configurations.split_first() {
+            Some((first, rest)) => {
+                witness.push(first.clone());
+                rest
+            }
+            None => return witness,
+        };
 
-        for i in 2..n_steps {
-            let next_witness = CommitNode::witness(context).unwrap();
-            let verify = if i == 2 {
-                verify_first_step.clone()
-            } else {
-                verify_step.clone()
-            };
-            let verify_next_witness = CommitNode::comp(context, verify, next_witness).unwrap();
-            let drop_and_verify =
-                CommitNode::pair(context, drop_c_c.clone(), verify_next_witness).unwrap();
+        for k in (0..usize::BITS).rev() {
+            if rest.len() & (1 << k) != 0 {
+                let (chunk, next) = rest.split_at(1 << k);
+                witness.push(tree(chunk));
+                rest = next;
+            }
+        }
+
+        witness
+    }
 
-            verify_prefix = CommitNode::comp(context, verify_prefix, drop_and_verify).unwrap();
+    /// Check the given computation natively, configuration by configuration.
+    ///
+    /// The checks are the same as in the program of [`DynVerifier::verify_computation`],
+    /// but they are executed for each pair of consecutive configurations on its own.
+    /// Return the first check that fails, in the order of the computation,
+    /// together with the step at which it fails.
+    pub fn diagnose<I: IntoIterator<Item = Value>>(
+        &self,
+        context: &mut Context<Turing>,
+        computation: I,
+    ) -> Result<(), StepError> {
+        let configurations: Vec<Value> = computation.into_iter().collect();
+        let n_steps = configurations.len();
+        assert!(
+            n_steps > 1,
+            "There must be at least two steps (initial and accepting configuration)"
+        );
+
+        let program = self
+            .select_check(context)
+            .finalize(std::iter::empty())
+            .unwrap();
+        let select = |first: bool, step: bool, i: usize| {
+            let pair = Value::prod(configurations[i].clone(), configurations[i + 1].clone());
+            Value::prod(
+                Value::u1(step as u8),
+                Value::prod(Value::u1(first as u8), pair),
+            )
+        };
+
+        let checks = std::iter::once((0, select(true, false, 0)))
+            .chain((0..n_steps - 1).map(|i| (i, select(false, true, i))))
+            .chain(std::iter::once((
+                n_steps - 1,
+                select(false, false, n_steps - 2),
+            )));
+
+        for (step, input) in checks {
+            error::execute_on(&program, &input).map_err(|error| StepError { step, error })?;
         }
 
-        CommitNode::comp(context, verify_prefix, verify_last_step).unwrap()
+        Ok(())
     }
 }
 
+impl<'a> TapeChecks for DynVerifier<'a> {
+    fn computation(&self) -> &dyn DynComputation {
+        self.computation
+    }
+
+    /// The configurations have a single tape: `C = M × (L × K)`.
+    fn on_tape(
+        &self,
+        context: &mut Context<Turing>,
+        _tape: usize,
+        next: bool,
+        inner: Rc<CommitNode<Turing>>,
+    ) -> Rc<CommitNode<Turing>> {
+        let drop_m_lk = CommitNode::drop(context, inner).unwrap();
+
+        if next {
+            CommitNode::drop(context, drop_m_lk).unwrap()
+        } else {
+            CommitNode::take(context, drop_m_lk).unwrap()
+        }
+    }
+
+    /// `C × C → 2^s × M`
+    fn pair_b_q(&self, context: &mut Context<Turing>, next: bool) -> Rc<CommitNode<Turing>> {
+        let iden_lk = CommitNode::iden(context).unwrap();
+        let pair_w_i = self.on_tape(context, 0, next, iden_lk);
+        let get = self.computation.get(context);
+        let b = CommitNode::comp(context, pair_w_i, get).unwrap();
+        let q = self.q(context, next);
+
+        CommitNode::pair(context, b, q).unwrap()
+    }
+
+    /// The rejecting state with [`DynVerifier::with_rejection`] and the accepting state otherwise.
+    fn final_state(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        if self.rejection {
+            self.machine.rejecting(context).unwrap()
+        } else {
+            self.machine.accepting(context)
+        }
+    }
+
+    fn rejection(&self) -> bool {
+        self.rejection
+    }
+
+    fn stutter(&self) -> bool {
+        self.stutter
+    }
+}
+
+/// Return a program that verifies `n_steps` witness configurations,
+/// given the checks of the first pair, of each pair and of the last pair of consecutive configurations.
+///
+/// If there is an `output` program, then it runs on the last pair of configurations after the checks
+/// and its result becomes the output of the program.
+fn verify_witnesses(
+    context: &mut Context<Turing>,
+    n_steps: usize,
+    verify_first: Rc<CommitNode<Turing>>,
+    verify_step: Rc<CommitNode<Turing>>,
+    verify_last: Rc<CommitNode<Turing>>,
+    output: Option<Rc<CommitNode<Turing>>>,
+) -> Rc<CommitNode<Turing>> {
+    assert!(
+        n_steps > 1,
+        "There must be at least two steps (initial and accepting configuration)"
+    );
+
+    let witness_0 = CommitNode::witness(context).unwrap();
+    let witness_1 = CommitNode::witness(context).unwrap();
+    let first_witnesses = CommitNode::pair(context, witness_0, witness_1).unwrap();
+
+    let verify_first_step = CommitNode::pair(context, verify_first, verify_step.clone()).unwrap();
+    let verify_last_step =
+        CommitNode::pair(context, verify_last.clone(), verify_step.clone()).unwrap();
+
+    let iden_c = CommitNode::iden(context).unwrap();
+    let drop_c_c = CommitNode::drop(context, iden_c).unwrap();
+
+    let then_output = |context: &mut Context<Turing>, verify: Rc<CommitNode<Turing>>| match &output
+    {
+        None => verify,
+        Some(output) => {
+            let verify_output = CommitNode::pair(context, verify, output.clone()).unwrap();
+            let iden_o = CommitNode::iden(context).unwrap();
+            let drop_o = CommitNode::drop(context, iden_o).unwrap();
+            CommitNode::comp(context, verify_output, drop_o).unwrap()
+        }
+    };
+
+    if n_steps == 2 {
+        let verify = CommitNode::pair(context, verify_first_step, verify_last).unwrap();
+        let verify = then_output(context, verify);
+        return CommitNode::comp(context, first_witnesses, verify).unwrap();
+    }
+
+    let verify_last_step = then_output(context, verify_last_step);
+
+    let mut verify_prefix = first_witnesses;
+
+    for i in 2..n_steps {
+        let next_witness = CommitNode::witness(context).unwrap();
+        let verify = if i == 2 {
+            verify_first_step.clone()
+        } else {
+            verify_step.clone()
+        };
+        let verify_next_witness = CommitNode::comp(context, verify, next_witness).unwrap();
+        let drop_and_verify =
+            CommitNode::pair(context, drop_c_c.clone(), verify_next_witness).unwrap();
+
+        verify_prefix = CommitNode::comp(context, verify_prefix, drop_and_verify).unwrap();
+    }
+
+    CommitNode::comp(context, verify_prefix, verify_last_step).unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::computation::Computation256;
-    use crate::machine::TwoBeavers;
+    use crate::computation::{Computation256, Computation256Binary};
+    use crate::machine::{TableMachine, TwoBeavers};
+    use crate::simulator::{self, Simulator, Trace};
+    use crate::util;
+
+    fn two_beavers_trace() -> (TableMachine, Trace) {
+        let machine: TableMachine = "1RB1LB_1LA1RZ".parse().unwrap();
+        let mut simulator = Simulator::new(&machine, [0; 32], 128);
+        simulator.run_until_accepting(100).unwrap();
+        let trace = simulator.into_trace();
+        (machine, trace)
+    }
+
+    fn verify(machine: &TableMachine, trace: &Trace) -> Result<(), VerificationError> {
+        let mut context = Context::default();
+        let verifier = DynVerifier::new(&PhantomData::<Computation256>, machine);
+        let commit = verifier.verify_computation(&mut context, trace.len());
+        let program = commit
+            .finalize(trace.computation256(|q| machine.state_value(q as usize)))
+            .unwrap();
+
+        execute(&program)
+    }
 
     #[test]
     fn type_check() {
         let mut context = Context::default();
+        let verifier = DynVerifier::new(&PhantomData::<Computation256>, &PhantomData::<TwoBeavers>);
+
+        let program = verifier
+            .pair_b_q(&mut context, false)
+            .finalize(std::iter::empty())
+            .unwrap();
+        println!("{}", program.ty);
+
+        let state = verifier.machine.state(&mut context);
+        let eq_state = verifier.machine.eq_state(&mut context);
+        let program = verifier
+            .check_state(&mut context, state, eq_state)
+            .finalize(std::iter::empty())
+            .unwrap();
+        println!("{}", program.ty);
+
+        let left = verifier.machine.left(&mut context);
+        let stay = verifier.machine.stay(&mut context);
+        let program = verifier
+            .check_index(&mut context, 0, left, stay)
+            .finalize(std::iter::empty())
+            .unwrap();
+        println!("{}", program.ty);
+
+        let write = verifier.machine.write(&mut context);
+        let program = verifier
+            .check_tape(&mut context, 0, write)
+            .finalize(std::iter::empty())
+            .unwrap();
+        println!("{}", program.ty);
+
+        let program = verifier
+            .verify_step(&mut context)
+            .finalize(std::iter::empty())
+            .unwrap();
+        println!("{}", program.ty);
+
+        let program = verifier
+            .verify_first(&mut context)
+            .finalize(std::iter::empty())
+            .unwrap();
+        println!("{}", program.ty);
+
+        let program = verifier
+            .verify_last(&mut context)
+            .finalize(std::iter::empty())
+            .unwrap();
+        println!("{}", program.ty);
+    }
+
+    #[test]
+    fn dyn_type_check() {
+        let mut context = Context::default();
+        let machine: TableMachine = "1RB1LB_1LA1RZ".parse().unwrap();
+        let verifier = DynVerifier::new(&PhantomData::<Computation256>, &machine);
 
-        let program = Verifier::<Computation256, TwoBeavers>::pair_b_q(&mut context)
+        let program = verifier
+            .pair_b_q(&mut context, false)
             .finalize(std::iter::empty())
             .unwrap();
         println!("{}", program.ty);
 
-        let program = Verifier::<Computation256, TwoBeavers>::check_state(&mut context)
+        let state = verifier.machine.state(&mut context);
+        let eq_state = verifier.machine.eq_state(&mut context);
+        let program = verifier
+            .check_state(&mut context, state, eq_state)
             .finalize(std::iter::empty())
             .unwrap();
         println!("{}", program.ty);
 
-        let program = Verifier::<Computation256, TwoBeavers>::check_index(&mut context)
+        let left = verifier.machine.left(&mut context);
+        let stay = verifier.machine.stay(&mut context);
+        let program = verifier
+            .check_index(&mut context, 0, left, stay)
             .finalize(std::iter::empty())
             .unwrap();
         println!("{}", program.ty);
 
-        let program = Verifier::<Computation256, TwoBeavers>::check_tape(&mut context)
+        let write = verifier.machine.write(&mut context);
+        let program = verifier
+            .check_tape(&mut context, 0, write)
             .finalize(std::iter::empty())
             .unwrap();
         println!("{}", program.ty);
 
-        let program = Verifier::<Computation256, TwoBeavers>::verify_step(&mut context)
+        let program = verifier
+            .verify_step(&mut context)
             .finalize(std::iter::empty())
             .unwrap();
         println!("{}", program.ty);
 
-        let program = Verifier::<Computation256, TwoBeavers>::verify_first(&mut context)
+        let program = verifier
+            .verify_first(&mut context)
             .finalize(std::iter::empty())
             .unwrap();
         println!("{}", program.ty);
 
-        let program = Verifier::<Computation256, TwoBeavers>::verify_last(&mut context)
+        let program = verifier
+            .verify_last(&mut context)
             .finalize(std::iter::empty())
             .unwrap();
         println!("{}", program.ty);
     }
+
+    #[test]
+    fn verification_error() {
+        let (machine, trace) = two_beavers_trace();
+        assert_eq!(Ok(()), verify(&machine, &trace));
+
+        let mut invalid_state = trace.clone();
+        invalid_state.states[1] = 0;
+        let mut invalid_index = trace.clone();
+        invalid_index.indices[2] = simulator::one_hot(0);
+        let mut invalid_tape = trace.clone();
+        invalid_tape.tapes[2][31] ^= 1;
+        let mut invalid_initial_state = trace.clone();
+        invalid_initial_state.states[0] = 1;
+        let mut invalid_accepting_state = trace.clone();
+        invalid_accepting_state.states.pop();
+        invalid_accepting_state.tapes.pop();
+        invalid_accepting_state.indices.pop();
+        let mut zero_index = trace.clone();
+        zero_index.indices[3] = [0; 32];
+        let mut two_hot_index = trace.clone();
+        two_hot_index.indices[0][0] = 0b10000000;
+
+        let trace_error = vec![
+            (zero_index, VerificationError::MalformedIndex),
+            (two_hot_index, VerificationError::MalformedIndex),
+            (invalid_state, VerificationError::InvalidState),
+            (invalid_index, VerificationError::InvalidIndex),
+            (invalid_tape, VerificationError::InvalidTape),
+            (
+                invalid_initial_state,
+                VerificationError::InvalidInitialState,
+            ),
+            (
+                invalid_accepting_state,
+                VerificationError::InvalidAcceptingState,
+            ),
+        ];
+
+        for (trace, error) in trace_error {
+            assert_eq!(Err(error), verify(&machine, &trace));
+        }
+    }
+
+    #[test]
+    fn blank_start() {
+        let (machine, trace) = two_beavers_trace();
+        let verify_blank = |trace: &Trace, start: usize| {
+            let mut context = Context::default();
+            let verifier =
+                DynVerifier::new(&PhantomData::<Computation256>, &machine).with_blank_start(start);
+            let commit = verifier.verify_computation(&mut context, trace.len());
+            let program = commit
+                .finalize(trace.computation256(|q| machine.state_value(q as usize)))
+                .unwrap();
+
+            execute(&program)
+        };
+        assert_eq!(Ok(()), verify_blank(&trace, 128));
+        assert_eq!(
+            Err(VerificationError::InvalidInitialIndex),
+            verify_blank(&trace, 0)
+        );
+
+        let mut simulator = Simulator::new(&machine, simulator::one_hot(0), 128);
+        simulator.run_until_accepting(100).unwrap();
+        let nonblank = simulator.into_trace();
+        assert_eq!(Ok(()), verify(&machine, &nonblank));
+        assert_eq!(
+            Err(VerificationError::InvalidInitialTape),
+            verify_blank(&nonblank, 128)
+        );
+    }
+
+    #[test]
+    fn input_output() {
+        let (machine, trace) = two_beavers_trace();
+        let output = *trace.tapes.last().unwrap();
+        let verify_relation = |input: &[u8], start: usize, output: &[u8]| {
+            let mut context = Context::default();
+            let verifier = DynVerifier::new(&PhantomData::<Computation256>, &machine)
+                .with_input(input, start)
+                .with_output(output);
+            let commit = verifier.verify_computation(&mut context, trace.len());
+            let program = commit
+                .finalize(trace.computation256(|q| machine.state_value(q as usize)))
+                .unwrap();
+
+            execute(&program)
+        };
+
+        assert_eq!(Ok(()), verify_relation(&[0; 32], 128, &output));
+        assert_eq!(
+            Err(VerificationError::InvalidInitialTape),
+            verify_relation(&[0xff; 32], 128, &output)
+        );
+        assert_eq!(
+            Err(VerificationError::InvalidInitialIndex),
+            verify_relation(&[0; 32], 127, &output)
+        );
+        assert_eq!(
+            Err(VerificationError::InvalidFinalTape),
+            verify_relation(&[0; 32], 128, &[0; 32])
+        );
+    }
+
+    #[test]
+    fn final_tape_output() {
+        let (machine, trace) = two_beavers_trace();
+        let output = |trace: &Trace| {
+            let mut context = Context::default();
+            let verifier = DynVerifier::new(&PhantomData::<Computation256>, &machine);
+            let commit = verifier.verify_computation_output(&mut context, trace.len());
+            let program = commit
+                .finalize(trace.computation256(|q| machine.state_value(q as usize)))
+                .unwrap();
+
+            execute_output(&program)
+        };
+
+        let final_tape = Computation256::tape_value(trace.tapes.last().unwrap()).unwrap();
+        assert_eq!(Ok(final_tape), output(&trace));
+
+        let mut invalid_tape = trace.clone();
+        invalid_tape.tapes[2][0] ^= 0x01;
+        assert_eq!(Err(VerificationError::InvalidTape), output(&invalid_tape));
+    }
+
+    #[test]
+    fn stutter() {
+        let (machine, trace) = two_beavers_trace();
+        let verify_bounded = |trace: &Trace| {
+            let mut context = Context::default();
+            let verifier =
+                DynVerifier::new(&PhantomData::<Computation256>, &machine).with_stutter();
+            let commit = verifier.verify_computation(&mut context, trace.len());
+            let program = commit
+                .finalize(trace.computation256(|q| machine.state_value(q as usize)))
+                .unwrap();
+
+            execute(&program)
+        };
+
+        assert_eq!(Ok(()), verify_bounded(&trace));
+        let padded = trace.stutter(trace.len() + 3);
+        assert_eq!(Ok(()), verify_bounded(&padded));
+        assert!(verify(&machine, &padded).is_err());
+
+        let mut moved = padded.clone();
+        moved.tapes[trace.len()][0] ^= 0x01;
+        assert_eq!(Err(VerificationError::InvalidTape), verify_bounded(&moved));
+        let mut moved = padded;
+        moved.indices[trace.len() + 1] = simulator::one_hot(0);
+        assert_eq!(Err(VerificationError::InvalidIndex), verify_bounded(&moved));
+    }
+
+    #[test]
+    fn shared() {
+        let (machine, trace) = two_beavers_trace();
+        let verify_shared = |trace: &Trace| {
+            let mut context = Context::default();
+            let verifier = DynVerifier::new(&PhantomData::<Computation256>, &machine);
+            let commit = verifier.verify_computation_shared(&mut context, trace.len());
+            let witness = DynVerifier::shared_witness(
+                trace.computation256(|q| machine.state_value(q as usize)),
+            );
+            let program = commit.finalize(witness.into_iter()).unwrap();
+
+            execute(&program)
+        };
+
+        assert_eq!(Ok(()), verify_shared(&trace));
+        let mut invalid_tape = trace.clone();
+        invalid_tape.tapes[3][0] ^= 0x01;
+        assert_eq!(
+            Err(VerificationError::InvalidTape),
+            verify_shared(&invalid_tape)
+        );
+        let mut invalid_state = trace.clone();
+        invalid_state.states[0] = 1;
+        assert_eq!(
+            Err(VerificationError::InvalidInitialState),
+            verify_shared(&invalid_state)
+        );
+
+        let program_size = |n_steps: usize| {
+            let mut context = Context::default();
+            let verifier = DynVerifier::new(&PhantomData::<Computation256>, &machine);
+            let commit = verifier.verify_computation_shared(&mut context, n_steps);
+            util::encode_base64(&commit).len()
+        };
+        assert!(program_size(1 << 16) < 2 * program_size(1 << 8));
+    }
+
+    #[test]
+    fn binary_head_off_edge() {
+        type C = Computation256Binary;
+        let cells_error = [
+            ("1LZ1LZ", 0, 255, VerificationError::HeadOffLeftEdge),
+            ("1RZ1RZ", 255, 0, VerificationError::HeadOffRightEdge),
+        ];
+
+        for (input, start, wrapped, error) in cells_error {
+            let machine: TableMachine = input.parse().unwrap();
+            let mut tape = [0; 32];
+            tape[start / 8] = 0b10000000 >> (start % 8);
+            let configuration = |q: usize, tape: &[u8], cell: usize| {
+                Value::prod(
+                    machine.state_value(q),
+                    Value::prod(C::tape_value(tape).unwrap(), C::index_value(cell).unwrap()),
+                )
+            };
+            let computation = [
+                configuration(0, &[0; 32], start),
+                configuration(1, &tape, wrapped),
+            ];
+
+            let mut context = Context::default();
+            let program = DynVerifier::new(&PhantomData::<C>, &machine)
+                .with_blank_start(start)
+                .with_halting()
+                .verify_computation(&mut context, computation.len())
+                .finalize(computation.into_iter())
+                .unwrap();
+            assert_eq!(Err(error), execute(&program));
+        }
+    }
+
+    #[test]
+    fn head_off_edge() {
+        let input_cell_error = vec![
+            ("1LZ1LZ", 0, VerificationError::HeadOffLeftEdge),
+            ("1RZ1RZ", 255, VerificationError::HeadOffRightEdge),
+        ];
+
+        for (input, cell, error) in input_cell_error {
+            let machine = input.parse::<TableMachine>().unwrap();
+            let mut tape = [0; 32];
+            tape[cell / 8] = simulator::one_hot::<32>(cell)[cell / 8];
+            let trace = Trace::new(
+                vec![0, 1],
+                vec![[0; 32], tape],
+                vec![simulator::one_hot(cell), simulator::one_hot(cell)],
+            );
+
+            assert_eq!(Err(error), verify(&machine, &trace));
+        }
+    }
+
+    #[test]
+    fn diagnose() {
+        let (machine, trace) = two_beavers_trace();
+        let verifier = DynVerifier::new(&PhantomData::<Computation256>, &machine);
+        let diagnose = |trace: &Trace| {
+            let mut context = Context::default();
+            let computation = trace.computation256(|q| machine.state_value(q as usize));
+            verifier.diagnose(&mut context, computation)
+        };
+        assert_eq!(Ok(()), diagnose(&trace));
+
+        let mut invalid_state = trace.clone();
+        invalid_state.states[4] = 2;
+        let mut invalid_tape = trace.clone();
+        invalid_tape.tapes[5][0] ^= 1;
+        let mut invalid_initial_state = trace.clone();
+        invalid_initial_state.states[0] = 1;
+        let mut invalid_accepting_state = trace.clone();
+        invalid_accepting_state.states.pop();
+        invalid_accepting_state.tapes.pop();
+        invalid_accepting_state.indices.pop();
+        let mut zero_index = trace.clone();
+        zero_index.indices[3] = [0; 32];
+
+        let trace_error = vec![
+            (zero_index, 2, VerificationError::MalformedIndex),
+            (invalid_state, 3, VerificationError::InvalidState),
+            (invalid_tape, 4, VerificationError::InvalidTape),
+            (
+                invalid_initial_state,
+                0,
+                VerificationError::InvalidInitialState,
+            ),
+            (
+                invalid_accepting_state,
+                5,
+                VerificationError::InvalidAcceptingState,
+            ),
+        ];
+
+        for (trace, step, error) in trace_error {
+            assert_eq!(Err(StepError { step, error }), diagnose(&trace));
+        }
+    }
 }
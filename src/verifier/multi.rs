@@ -0,0 +1,494 @@
+use super::{verify_witnesses, TapeChecks, VerificationError};
+use crate::computation::DynComputation;
+use crate::jet::application::Turing;
+use crate::machine::MultiTapeMachine;
+use simplicity::core::Context;
+use simplicity::CommitNode;
+use std::rc::Rc;
+
+/// Produces Simplicity programs that verify
+/// that a given Turing machine with `k` tapes computes a given computation.
+///
+/// A configuration consists of the current state and of a tape and index for each tape:
+/// `M × T` with `T = (L × K) × (… × (L × K))` nested to the right, starting with the first tape.
+/// Each tape and index is in the format of the computation.
+/// For `k = 1`, configurations are the same as for [`super::DynVerifier`].
+pub struct MultiTapeVerifier<'a> {
+    computation: &'a dyn DynComputation,
+    machine: &'a dyn MultiTapeMachine,
+    start: Option<usize>,
+    input: Option<Vec<u8>>,
+    output: Option<Vec<u8>>,
+    score: Option<u16>,
+    rejection: bool,
+    halting: bool,
+    stutter: bool,
+}
+
+impl<'a> MultiTapeVerifier<'a> {
+    /// Create a verifier for the given computation format and machine.
+    pub fn new(computation: &'a dyn DynComputation, machine: &'a dyn MultiTapeMachine) -> Self {
+        assert!(machine.n_tapes() > 0, "There must be at least one tape");
+        Self {
+            computation,
+            machine,
+            start: None,
+            input: None,
+            output: None,
+            score: None,
+            rejection: false,
+            halting: false,
+            stutter: false,
+        }
+    }
+
+    /// Additionally require that the computation starts on blank tapes with each index at the `start` cell.
+    ///
+    /// By default, only the first state is constrained.
+    /// The computation format must support [`DynComputation::is_blank_tape`] and [`DynComputation::index_value`].
+    /// Cannot be combined with [`MultiTapeVerifier::with_input`].
+    pub fn with_blank_start(mut self, start: usize) -> Self {
+        assert!(
+            self.start.is_none(),
+            "The start of the computation is already constrained"
+        );
+        assert!(
+            self.computation
+                .is_blank_tape(&mut Context::default())
+                .is_some(),
+            "The computation format cannot check for blank tapes"
+        );
+        assert!(
+            self.computation.index_value(start).is_some(),
+            "The computation format cannot encode indices"
+        );
+        self.start = Some(start);
+        self
+    }
+
+    /// Additionally require that the computation starts with the `input` on the first tape,
+    /// blank other tapes and each index at the `start` cell.
+    ///
+    /// The tape is given in the format of [`DynComputation::tape_value`] and hard-coded into the program.
+    /// The computation format must support [`DynComputation::index_value`] and [`DynComputation::tape_value`].
+    /// Cannot be combined with [`MultiTapeVerifier::with_blank_start`].
+    pub fn with_input(mut self, input: &[u8], start: usize) -> Self {
+        assert!(
+            self.start.is_none(),
+            "The start of the computation is already constrained"
+        );
+        if self.machine.n_tapes() > 1 {
+            assert!(
+                self.computation
+                    .is_blank_tape(&mut Context::default())
+                    .is_some(),
+                "The computation format cannot check for blank tapes"
+            );
+        }
+        assert!(
+            self.computation.index_value(start).is_some(),
+            "The computation format cannot encode indices"
+        );
+        assert!(
+            self.computation.tape_value(input).is_some(),
+            "The computation format cannot encode tapes"
+        );
+        self.start = Some(start);
+        self.input = Some(input.to_vec());
+        self
+    }
+
+    /// Additionally require that the computation ends with the `output` on the first tape.
+    ///
+    /// The tape is given in the format of [`DynComputation::tape_value`] and hard-coded into the program.
+    /// The computation format must support [`DynComputation::tape_value`].
+    pub fn with_output(mut self, output: &[u8]) -> Self {
+        assert!(
+            self.computation.tape_value(output).is_some(),
+            "The computation format cannot encode tapes"
+        );
+        self.output = Some(output.to_vec());
+        self
+    }
+
+    /// Additionally require that the computation ends with exactly `ones` nonblank symbols on the first tape.
+    ///
+    /// The computation format must support [`DynComputation::count_nonblank`].
+    pub fn with_score(mut self, ones: u16) -> Self {
+        assert!(
+            self.computation
+                .count_nonblank(&mut Context::default())
+                .is_some(),
+            "The computation format cannot count nonblank symbols"
+        );
+        self.score = Some(ones);
+        self
+    }
+
+    /// Require that the computation ends in the rejecting state instead of the accepting state.
+    ///
+    /// The machine must support [`MultiTapeMachine::rejecting`].
+    pub fn with_rejection(mut self) -> Self {
+        assert!(
+            self.machine.rejecting(&mut Context::default()).is_some(),
+            "The machine has no rejecting state"
+        );
+        self.rejection = true;
+        self
+    }
+
+    /// Require that the machine halts in the last configuration,
+    /// i.e., that it has no transition for the symbols under the heads.
+    ///
+    /// As in [`super::DynVerifier::with_halting`], any halting configuration is accepted,
+    /// unless [`MultiTapeVerifier::with_rejection`] requires a rejecting state.
+    ///
+    /// Machines that don't support [`MultiTapeMachine::halts`] never halt, so their programs always fail.
+    pub fn with_halting(mut self) -> Self {
+        self.halting = true;
+        self
+    }
+
+    /// Allow configurations in the final state to be followed by the same configuration,
+    /// as in [`super::DynVerifier::with_stutter`].
+    pub fn with_stutter(mut self) -> Self {
+        self.stutter = true;
+        self
+    }
+
+    fn verify_index_encodings(
+        &self,
+        context: &mut Context<Turing>,
+        next: bool,
+    ) -> Rc<CommitNode<Turing>> {
+        let verify_index_encodings = (0..self.machine.n_tapes())
+            .map(|tape| self.verify_index_encoding(context, tape, next))
+            .collect();
+
+        pair_all(context, verify_index_encodings)
+    }
+
+    fn verify_step(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        let verify_index_encodings = self.verify_index_encodings(context, true);
+
+        let state = self.machine.state(context);
+        let eq_state = self.machine.eq_state(context);
+        let check_state = self.check_state(context, state, eq_state);
+        let verify_state =
+            CommitNode::assert(context, check_state, VerificationError::InvalidState.cmr())
+                .unwrap();
+
+        let verify_tapes = (0..self.machine.n_tapes())
+            .map(|tape| {
+                let left = self.machine.left(context, tape);
+                let stay = self.machine.stay(context, tape);
+                let write = self.machine.write(context, tape);
+                self.verify_tape_step(context, tape, left, stay, write)
+            })
+            .collect();
+        let verify_tapes = pair_all(context, verify_tapes);
+
+        let verify_transition = CommitNode::pair(context, verify_state, verify_tapes).unwrap();
+        let halts = self.machine.halts(context);
+        let verify_transition = match self.verify_defined(context, halts) {
+            None => verify_transition,
+            Some(verify_defined) => {
+                CommitNode::pair(context, verify_defined, verify_transition).unwrap()
+            }
+        };
+
+        CommitNode::pair(context, verify_index_encodings, verify_transition).unwrap()
+    }
+
+    fn verify_first(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        let initial = self.machine.initial(context);
+        let verify_initial_state = self.verify_initial_state(context, initial);
+        let verify_index_encodings = self.verify_index_encodings(context, false);
+        let verify_first =
+            CommitNode::pair(context, verify_initial_state, verify_index_encodings).unwrap();
+
+        match self.start {
+            None => verify_first,
+            Some(start) => {
+                let verify_starts = (0..self.machine.n_tapes())
+                    .map(|tape| {
+                        let input = self.input.as_deref().filter(|_| tape == 0);
+                        self.verify_start(context, tape, start, input)
+                    })
+                    .collect();
+                let verify_starts = pair_all(context, verify_starts);
+                CommitNode::pair(context, verify_first, verify_starts).unwrap()
+            }
+        }
+    }
+
+    fn verify_last(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        // Any halting configuration is final, unless it must reject
+        let verify_final_state = if self.halting {
+            let halts = self.machine.halts(context);
+            let verify_halted = self.verify_halted(context, halts);
+            if self.rejection {
+                let verify_final_state = self.verify_final_state(context);
+                CommitNode::pair(context, verify_final_state, verify_halted).unwrap()
+            } else {
+                verify_halted
+            }
+        } else {
+            self.verify_final_state(context)
+        };
+
+        let verify_final_state = match self.score {
+            None => verify_final_state,
+            Some(ones) => {
+                let verify_score = self.verify_score(context, ones);
+                CommitNode::pair(context, verify_final_state, verify_score).unwrap()
+            }
+        };
+
+        match &self.output {
+            None => verify_final_state,
+            Some(output) => {
+                let verify_output = self.verify_output(context, output);
+                CommitNode::pair(context, verify_final_state, verify_output).unwrap()
+            }
+        }
+    }
+
+    /// Return a program that verifies that the Turing machine computes a computation of `n_steps`.
+    ///
+    /// The program fails if the validation fails and does nothing otherwise.
+    pub fn verify_computation(
+        &self,
+        context: &mut Context<Turing>,
+        n_steps: usize,
+    ) -> Rc<CommitNode<Turing>> {
+        let verify_first = self.verify_first(context);
+        let verify_step = self.verify_step(context);
+        let verify_last = self.verify_last(context);
+
+        verify_witnesses(
+            context,
+            n_steps,
+            verify_first,
+            verify_step,
+            verify_last,
+            None,
+        )
+    }
+}
+
+impl<'a> TapeChecks for MultiTapeVerifier<'a> {
+    fn computation(&self) -> &dyn DynComputation {
+        self.computation
+    }
+
+    /// The tapes are nested to the right: `C = M × ((L × K) × (… × (L × K)))`.
+    fn on_tape(
+        &self,
+        context: &mut Context<Turing>,
+        tape: usize,
+        next: bool,
+        inner: Rc<CommitNode<Turing>>,
+    ) -> Rc<CommitNode<Turing>> {
+        let mut on_tape = if tape + 1 < self.machine.n_tapes() {
+            CommitNode::take(context, inner).unwrap()
+        } else {
+            inner
+        };
+        for _ in 0..tape {
+            on_tape = CommitNode::drop(context, on_tape).unwrap();
+        }
+        let drop_m_t = CommitNode::drop(context, on_tape).unwrap();
+
+        if next {
+            CommitNode::drop(context, drop_m_t).unwrap()
+        } else {
+            CommitNode::take(context, drop_m_t).unwrap()
+        }
+    }
+
+    fn pair_b_q(&self, context: &mut Context<Turing>, next: bool) -> Rc<CommitNode<Turing>> {
+        let bs = (0..self.machine.n_tapes())
+            .map(|tape| {
+                let iden_lk = CommitNode::iden(context).unwrap();
+                let pair_w_i = self.on_tape(context, tape, next, iden_lk);
+                let get = self.computation.get(context);
+                CommitNode::comp(context, pair_w_i, get).unwrap()
+            })
+            .collect();
+        let bs = pair_all(context, bs);
+        let q = self.q(context, next);
+
+        CommitNode::pair(context, bs, q).unwrap()
+    }
+
+    /// The rejecting state with [`MultiTapeVerifier::with_rejection`] and the accepting state otherwise.
+    fn final_state(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        if self.rejection {
+            self.machine.rejecting(context).unwrap()
+        } else {
+            self.machine.accepting(context)
+        }
+    }
+
+    fn rejection(&self) -> bool {
+        self.rejection
+    }
+
+    fn stutter(&self) -> bool {
+        self.stutter
+    }
+}
+
+/// Pair the given programs, nested to the right: `A → B_0 × (B_1 × … × B_(n - 1))`.
+///
+/// There must be at least one program.
+fn pair_all(
+    context: &mut Context<Turing>,
+    programs: Vec<Rc<CommitNode<Turing>>>,
+) -> Rc<CommitNode<Turing>> {
+    let mut programs = programs.into_iter().rev();
+    let last = programs.next().expect("There must be at least one program");
+
+    programs.fold(last, |rest, program| {
+        CommitNode::pair(context, program, rest).unwrap()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::computation::Computation256;
+    use crate::machine::{Direction, MultiTapeTableMachine, MultiTapeTransition};
+    use crate::simulator::{self, MultiTapeSimulator, Trace};
+    use crate::{execute, util};
+    use simplicity::core::Value;
+    use std::marker::PhantomData;
+
+    /// Machine with two tapes that copies the ones at the head of the first tape onto the second tape
+    fn copy() -> MultiTapeTableMachine {
+        use Direction::*;
+        let t = MultiTapeTransition::new;
+        let transitions = vec![
+            vec![
+                t(vec![0, 0], vec![Stay, Stay], 1),
+                t(vec![0, 1], vec![Stay, Stay], 1),
+                t(vec![1, 1], vec![Right, Right], 0),
+                t(vec![1, 1], vec![Right, Right], 0),
+            ],
+            vec![
+                t(vec![0, 0], vec![Stay, Stay], 1),
+                t(vec![0, 1], vec![Stay, Stay], 1),
+                t(vec![1, 0], vec![Stay, Stay], 1),
+                t(vec![1, 1], vec![Stay, Stay], 1),
+            ],
+        ];
+        MultiTapeTableMachine::new(2, transitions, &[0], &[1])
+    }
+
+    fn input() -> [u8; 32] {
+        let mut input = [0; 32];
+        input[16] = 0b11111000;
+        input
+    }
+
+    fn computation(machine: &MultiTapeTableMachine, traces: &[Trace]) -> Vec<Value> {
+        let tapes = traces
+            .iter()
+            .map(|trace| trace.computation(|q| machine.state_value(q as usize)))
+            .collect();
+        util::multi_tape_computation(tapes).collect()
+    }
+
+    #[test]
+    fn input_output() {
+        let machine = copy();
+        let input = input();
+        let mut simulator = MultiTapeSimulator::new(&machine, &[input, [0; 32]], 128);
+        simulator.run_until_accepting(100).unwrap();
+        assert_eq!(&[133, 133], simulator.heads());
+        let traces = simulator.into_traces();
+        assert_eq!(input, *traces[1].tapes.last().unwrap());
+
+        let mut context = Context::default();
+        let verifier = MultiTapeVerifier::new(&PhantomData::<Computation256>, &machine)
+            .with_input(&input, 128)
+            .with_output(&input);
+        let commit = verifier.verify_computation(&mut context, traces[0].len());
+        let program = commit
+            .finalize(computation(&machine, &traces).into_iter())
+            .unwrap();
+        assert_eq!(Ok(()), execute(&program));
+
+        let mut invalid_tape = traces.clone();
+        invalid_tape[1].tapes[2][16] ^= 0b01000000;
+        let program = commit
+            .finalize(computation(&machine, &invalid_tape).into_iter())
+            .unwrap();
+        assert_eq!(Err(VerificationError::InvalidTape), execute(&program));
+
+        let mut invalid_index = traces.clone();
+        invalid_index[1].indices[1] = simulator::one_hot(128);
+        let program = commit
+            .finalize(computation(&machine, &invalid_index).into_iter())
+            .unwrap();
+        assert_eq!(Err(VerificationError::InvalidIndex), execute(&program));
+    }
+
+    #[test]
+    fn stutter_score() {
+        let machine = copy();
+        let mut simulator = MultiTapeSimulator::new(&machine, &[input(), [0; 32]], 128);
+        simulator.run_until_accepting(100).unwrap();
+        let traces: Vec<Trace> = simulator
+            .into_traces()
+            .iter()
+            .map(|trace| trace.stutter(trace.len() + 3))
+            .collect();
+
+        for (ones, expected) in [(5, Ok(())), (4, Err(VerificationError::InvalidScore))] {
+            let mut context = Context::default();
+            let program = MultiTapeVerifier::new(&PhantomData::<Computation256>, &machine)
+                .with_stutter()
+                .with_score(ones)
+                .verify_computation(&mut context, traces[0].len())
+                .finalize(computation(&machine, &traces).into_iter())
+                .unwrap();
+            assert_eq!(expected, execute(&program));
+        }
+
+        let mut context = Context::default();
+        let program = MultiTapeVerifier::new(&PhantomData::<Computation256>, &machine)
+            .with_halting()
+            .verify_computation(&mut context, traces[0].len())
+            .finalize(computation(&machine, &traces).into_iter())
+            .unwrap();
+        assert_eq!(Err(VerificationError::NotHalted), execute(&program));
+    }
+
+    #[test]
+    fn rejection() {
+        let machine = copy().with_rejecting(&[0]);
+        let verify = |traces: &[Trace]| {
+            let mut context = Context::default();
+            let program = MultiTapeVerifier::new(&PhantomData::<Computation256>, &machine)
+                .with_rejection()
+                .verify_computation(&mut context, traces[0].len())
+                .finalize(computation(&machine, traces).into_iter())
+                .unwrap();
+            execute(&program)
+        };
+
+        let mut simulator = MultiTapeSimulator::new(&machine, &[input(), [0; 32]], 128);
+        simulator.step().unwrap();
+        simulator.step().unwrap();
+        assert!(machine.is_rejecting(simulator.state()));
+        assert_eq!(Ok(()), verify(simulator.traces()));
+
+        simulator.run_until_accepting(100).unwrap();
+        assert_eq!(
+            Err(VerificationError::InvalidRejectingState),
+            verify(simulator.traces())
+        );
+    }
+}
@@ -0,0 +1,411 @@
+use super::VerificationError;
+use crate::computation::DynComputation;
+use crate::jet::application::Turing;
+use crate::util;
+use simplicity::core::{Context, Value};
+use simplicity::CommitNode;
+use std::rc::Rc;
+
+/// Checks on the tapes of a pair of consecutive configurations `C × C`,
+/// shared by [`super::DynVerifier`] and [`super::MultiTapeVerifier`].
+///
+/// Configurations consist of the current state and of one or more tapes with their indices: `C = M × T`.
+/// Each tape and index is in the format of the computation.
+pub(super) trait TapeChecks {
+    /// Return the format of the computation.
+    fn computation(&self) -> &dyn DynComputation;
+
+    /// Return a program that runs `inner` on the given tape of the current or `next` configuration.
+    ///
+    /// `C × C → X` where `inner: L × K → X`
+    fn on_tape(
+        &self,
+        context: &mut Context<Turing>,
+        tape: usize,
+        next: bool,
+        inner: Rc<CommitNode<Turing>>,
+    ) -> Rc<CommitNode<Turing>>;
+
+    /// Return a program that takes the symbols under the heads and the state
+    /// of the current or `next` configuration.
+    ///
+    /// `C × C → S × M`
+    fn pair_b_q(&self, context: &mut Context<Turing>, next: bool) -> Rc<CommitNode<Turing>>;
+
+    /// Check if the given state is the state in which the computation must end.
+    ///
+    /// `final_state: 2^m → 2`
+    fn final_state(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>>;
+
+    /// Check if the computation must end in the rejecting state instead of the accepting state.
+    fn rejection(&self) -> bool;
+
+    /// Check if configurations in the final state may be followed by the same configuration.
+    fn stutter(&self) -> bool;
+
+    /// `C × C → M`
+    fn q(&self, context: &mut Context<Turing>, next: bool) -> Rc<CommitNode<Turing>> {
+        let iden_m = CommitNode::iden(context).unwrap();
+        let take_m_t = CommitNode::take(context, iden_m).unwrap();
+
+        if next {
+            CommitNode::drop(context, take_m_t).unwrap()
+        } else {
+            CommitNode::take(context, take_m_t).unwrap()
+        }
+    }
+
+    /// `C × C → L`
+    fn w(&self, context: &mut Context<Turing>, tape: usize, next: bool) -> Rc<CommitNode<Turing>> {
+        let iden_l = CommitNode::iden(context).unwrap();
+        let take_l_k = CommitNode::take(context, iden_l).unwrap();
+        self.on_tape(context, tape, next, take_l_k)
+    }
+
+    /// `C × C → K`
+    fn i(&self, context: &mut Context<Turing>, tape: usize, next: bool) -> Rc<CommitNode<Turing>> {
+        let iden_k = CommitNode::iden(context).unwrap();
+        let drop_l_k = CommitNode::drop(context, iden_k).unwrap();
+        self.on_tape(context, tape, next, drop_l_k)
+    }
+
+    /// Return a program that selects the `unchanged` value instead of the `computed` value
+    /// if stutter steps are allowed and the first configuration is in the final state
+    /// (see [`TapeChecks::final_state`]).
+    fn or_stutter(
+        &self,
+        context: &mut Context<Turing>,
+        computed: Rc<CommitNode<Turing>>,
+        unchanged: Rc<CommitNode<Turing>>,
+    ) -> Rc<CommitNode<Turing>> {
+        if !self.stutter() {
+            return computed;
+        }
+
+        let q = self.q(context, false);
+        let final_state = self.final_state(context);
+        let is_final = CommitNode::comp(context, q, final_state).unwrap();
+
+        let iden_c_c = CommitNode::iden(context).unwrap();
+        let pair_is_final_c_c = CommitNode::pair(context, is_final, iden_c_c).unwrap();
+        let cond_unchanged_computed = CommitNode::cond(context, unchanged, computed).unwrap();
+
+        CommitNode::comp(context, pair_is_final_c_c, cond_unchanged_computed).unwrap()
+    }
+
+    /// Select the `unchanged` value instead of the `moved` one if the machine stays in place.
+    ///
+    /// `moved` is returned as is if there is no `stay` predicate (`S × M → 2`).
+    fn or_stay(
+        &self,
+        context: &mut Context<Turing>,
+        stay: Option<Rc<CommitNode<Turing>>>,
+        moved: Rc<CommitNode<Turing>>,
+        unchanged: Rc<CommitNode<Turing>>,
+    ) -> Rc<CommitNode<Turing>> {
+        let stay_predicate = match stay {
+            Some(stay_predicate) => stay_predicate,
+            None => return moved,
+        };
+        let pair_b_q = self.pair_b_q(context, false);
+        let stay = CommitNode::comp(context, pair_b_q, stay_predicate).unwrap();
+
+        let iden_c_c = CommitNode::iden(context).unwrap();
+        let pair_stay_c_c = CommitNode::pair(context, stay, iden_c_c).unwrap();
+        let cond_unchanged_moved = CommitNode::cond(context, unchanged, moved).unwrap();
+
+        CommitNode::comp(context, pair_stay_c_c, cond_unchanged_moved).unwrap()
+    }
+
+    /// Check that the state of the next configuration follows from the current one.
+    ///
+    /// `C × C → 2` where `state: S × M → M` and `eq_state: M × M → 2`
+    fn check_state(
+        &self,
+        context: &mut Context<Turing>,
+        state: Rc<CommitNode<Turing>>,
+        eq_state: Rc<CommitNode<Turing>>,
+    ) -> Rc<CommitNode<Turing>> {
+        let pair_b_q = self.pair_b_q(context, false);
+        let computed_q_prime = CommitNode::comp(context, pair_b_q, state).unwrap();
+        let q = self.q(context, false);
+        let computed_q_prime = self.or_stutter(context, computed_q_prime, q);
+        let q_prime = self.q(context, true);
+
+        let pair_computed_q_prime_q_prime =
+            CommitNode::pair(context, computed_q_prime, q_prime).unwrap();
+
+        CommitNode::comp(context, pair_computed_q_prime_q_prime, eq_state).unwrap()
+    }
+
+    /// Check that the index of the given tape of the next configuration follows from the current one.
+    ///
+    /// `C × C → 2` where `left, stay: S × M → 2`
+    fn check_index(
+        &self,
+        context: &mut Context<Turing>,
+        tape: usize,
+        left: Rc<CommitNode<Turing>>,
+        stay: Option<Rc<CommitNode<Turing>>>,
+    ) -> Rc<CommitNode<Turing>> {
+        let pair_b_q = self.pair_b_q(context, false);
+        let left = CommitNode::comp(context, pair_b_q, left).unwrap();
+        let i = self.i(context, tape, false);
+        let pair_left_i = CommitNode::pair(context, left, i.clone()).unwrap();
+
+        let dec_index = self.computation().dec_index(context);
+        let inc_index = self.computation().inc_index(context);
+        let cond_dec_inc = CommitNode::cond(context, dec_index, inc_index).unwrap();
+        let computed_i_prime = CommitNode::comp(context, pair_left_i, cond_dec_inc).unwrap();
+        let computed_i_prime = self.or_stay(context, stay, computed_i_prime, i.clone());
+        let computed_i_prime = self.or_stutter(context, computed_i_prime, i);
+
+        let i_prime = self.i(context, tape, true);
+        let pair_computed_i_prime_i_prime =
+            CommitNode::pair(context, computed_i_prime, i_prime).unwrap();
+        let eq_index = self.computation().eq_index(context);
+
+        CommitNode::comp(context, pair_computed_i_prime_i_prime, eq_index).unwrap()
+    }
+
+    /// Check that the given tape of the next configuration follows from the current one.
+    ///
+    /// `C × C → 2` where `write: S × M → 2^s`
+    fn check_tape(
+        &self,
+        context: &mut Context<Turing>,
+        tape: usize,
+        write: Rc<CommitNode<Turing>>,
+    ) -> Rc<CommitNode<Turing>> {
+        let pair_b_q = self.pair_b_q(context, false);
+        let b = CommitNode::comp(context, pair_b_q, write).unwrap();
+        let iden_lk = CommitNode::iden(context).unwrap();
+        let pair_w_i = self.on_tape(context, tape, false, iden_lk);
+        let pair_b_pair_w_i = CommitNode::pair(context, b, pair_w_i).unwrap();
+
+        let set = self.computation().set(context);
+        let computed_w_prime = CommitNode::comp(context, pair_b_pair_w_i, set).unwrap();
+        let w = self.w(context, tape, false);
+        let computed_w_prime = self.or_stutter(context, computed_w_prime, w);
+        let w_prime = self.w(context, tape, true);
+
+        let pair_computed_w_prime_w_prime =
+            CommitNode::pair(context, computed_w_prime, w_prime).unwrap();
+        let eq_tape = self.computation().eq_tape(context);
+
+        CommitNode::comp(context, pair_computed_w_prime_w_prime, eq_tape).unwrap()
+    }
+
+    /// Verify the index and the given tape of the next configuration.
+    ///
+    /// `C × C → 1 × 1`
+    fn verify_tape_step(
+        &self,
+        context: &mut Context<Turing>,
+        tape: usize,
+        left: Rc<CommitNode<Turing>>,
+        stay: Option<Rc<CommitNode<Turing>>>,
+        write: Rc<CommitNode<Turing>>,
+    ) -> Rc<CommitNode<Turing>> {
+        let check_index = self.check_index(context, tape, left, stay);
+        let verify_index =
+            CommitNode::assert(context, check_index, VerificationError::InvalidIndex.cmr())
+                .unwrap();
+
+        let check_tape = self.check_tape(context, tape, write);
+        let verify_tape =
+            CommitNode::assert(context, check_tape, VerificationError::InvalidTape.cmr()).unwrap();
+
+        CommitNode::pair(context, verify_index, verify_tape).unwrap()
+    }
+
+    fn verify_index_encoding(
+        &self,
+        context: &mut Context<Turing>,
+        tape: usize,
+        next: bool,
+    ) -> Rc<CommitNode<Turing>> {
+        let i = self.i(context, tape, next);
+        let is_valid_index = self.computation().is_valid_index(context);
+        let check_index_encoding = CommitNode::comp(context, i, is_valid_index).unwrap();
+
+        CommitNode::assert(
+            context,
+            check_index_encoding,
+            VerificationError::MalformedIndex.cmr(),
+        )
+        .unwrap()
+    }
+
+    /// Check that the first configuration is in the initial state.
+    ///
+    /// `C × C → 1` where `initial: 2^m → 2`
+    fn verify_initial_state(
+        &self,
+        context: &mut Context<Turing>,
+        initial: Rc<CommitNode<Turing>>,
+    ) -> Rc<CommitNode<Turing>> {
+        let q = self.q(context, false);
+        let check_first = CommitNode::comp(context, q, initial).unwrap();
+
+        CommitNode::assert(
+            context,
+            check_first,
+            VerificationError::InvalidInitialState.cmr(),
+        )
+        .unwrap()
+    }
+
+    /// `L → 2`
+    fn check_tape_value(
+        &self,
+        context: &mut Context<Turing>,
+        tape: &[u8],
+    ) -> Rc<CommitNode<Turing>> {
+        let iden_l = CommitNode::iden(context).unwrap();
+        let tape_value = self.computation().tape_value(tape).unwrap();
+        let scribe_tape = CommitNode::scribe(context, &tape_value).unwrap();
+        let pair_w_tape = CommitNode::pair(context, iden_l, scribe_tape).unwrap();
+        let eq_tape = self.computation().eq_tape(context);
+
+        CommitNode::comp(context, pair_w_tape, eq_tape).unwrap()
+    }
+
+    /// Check that the given tape of the first configuration is the `input` tape (or blank)
+    /// with the index at the `start` cell.
+    ///
+    /// `C × C → 1 × 1`
+    fn verify_start(
+        &self,
+        context: &mut Context<Turing>,
+        tape: usize,
+        start: usize,
+        input: Option<&[u8]>,
+    ) -> Rc<CommitNode<Turing>> {
+        let w = self.w(context, tape, false);
+        let check_input = match input {
+            None => self.computation().is_blank_tape(context).unwrap(),
+            Some(input) => self.check_tape_value(context, input),
+        };
+        let check_tape = CommitNode::comp(context, w, check_input).unwrap();
+        let verify_tape = CommitNode::assert(
+            context,
+            check_tape,
+            VerificationError::InvalidInitialTape.cmr(),
+        )
+        .unwrap();
+
+        let i = self.i(context, tape, false);
+        let start_index = self.computation().index_value(start).unwrap();
+        let scribe_start = CommitNode::scribe(context, &start_index).unwrap();
+        let pair_i_start = CommitNode::pair(context, i, scribe_start).unwrap();
+
+        let eq_index = self.computation().eq_index(context);
+        let check_index = CommitNode::comp(context, pair_i_start, eq_index).unwrap();
+        let verify_index = CommitNode::assert(
+            context,
+            check_index,
+            VerificationError::InvalidInitialIndex.cmr(),
+        )
+        .unwrap();
+
+        CommitNode::pair(context, verify_tape, verify_index).unwrap()
+    }
+
+    /// Check that the last configuration is in the final state (see [`TapeChecks::final_state`]).
+    ///
+    /// `C × C → 1`
+    fn verify_final_state(&self, context: &mut Context<Turing>) -> Rc<CommitNode<Turing>> {
+        let q_prime = self.q(context, true);
+        let final_state = self.final_state(context);
+        let check_last = CommitNode::comp(context, q_prime, final_state).unwrap();
+        let error = if self.rejection() {
+            VerificationError::InvalidRejectingState
+        } else {
+            VerificationError::InvalidAcceptingState
+        };
+
+        CommitNode::assert(context, check_last, error.cmr()).unwrap()
+    }
+
+    /// Check that the transition from the first configuration is defined, unless it is a stutter step.
+    ///
+    /// Return `None` if there is no `halts` predicate (`S × M → 2`), i.e., if the transition function is total.
+    fn verify_defined(
+        &self,
+        context: &mut Context<Turing>,
+        halts: Option<Rc<CommitNode<Turing>>>,
+    ) -> Option<Rc<CommitNode<Turing>>> {
+        let halts = halts?;
+        let pair_b_q = self.pair_b_q(context, false);
+        let halted = CommitNode::comp(context, pair_b_q, halts).unwrap();
+        let defined = CommitNode::not(context, halted).unwrap();
+        let bit_true = CommitNode::bit_true(context).unwrap();
+        let check_defined = self.or_stutter(context, defined, bit_true);
+
+        Some(
+            CommitNode::assert(
+                context,
+                check_defined,
+                VerificationError::UnexpectedHalt.cmr(),
+            )
+            .unwrap(),
+        )
+    }
+
+    /// Check that the last configuration has no transition.
+    ///
+    /// Machines without a `halts` predicate (`S × M → 2`) never halt, so the check always fails for them.
+    ///
+    /// `C × C → 1`
+    fn verify_halted(
+        &self,
+        context: &mut Context<Turing>,
+        halts: Option<Rc<CommitNode<Turing>>>,
+    ) -> Rc<CommitNode<Turing>> {
+        let check_halted = match halts {
+            Some(halts) => {
+                let pair_b_q = self.pair_b_q(context, true);
+                CommitNode::comp(context, pair_b_q, halts).unwrap()
+            }
+            None => CommitNode::bit_false(context).unwrap(),
+        };
+
+        CommitNode::assert(context, check_halted, VerificationError::NotHalted.cmr()).unwrap()
+    }
+
+    /// Check that the first tape of the last configuration holds exactly `ones` nonblank symbols.
+    ///
+    /// `C × C → 1`
+    fn verify_score(&self, context: &mut Context<Turing>, ones: u16) -> Rc<CommitNode<Turing>> {
+        let w_prime = self.w(context, 0, true);
+        let count_nonblank = self.computation().count_nonblank(context).unwrap();
+        let count = CommitNode::comp(context, w_prime, count_nonblank).unwrap();
+        let scribe_ones = CommitNode::scribe(context, &Value::u16(ones)).unwrap();
+        let pair_count_ones = CommitNode::pair(context, count, scribe_ones).unwrap();
+        let eq_word = util::eq_word(context, 16);
+        let check_score = CommitNode::comp(context, pair_count_ones, eq_word).unwrap();
+
+        CommitNode::assert(context, check_score, VerificationError::InvalidScore.cmr()).unwrap()
+    }
+
+    /// Check that the first tape of the last configuration is the `output` tape.
+    ///
+    /// `C × C → 1`
+    fn verify_output(
+        &self,
+        context: &mut Context<Turing>,
+        output: &[u8],
+    ) -> Rc<CommitNode<Turing>> {
+        let w_prime = self.w(context, 0, true);
+        let check_output = self.check_tape_value(context, output);
+        let check_tape = CommitNode::comp(context, w_prime, check_output).unwrap();
+
+        CommitNode::assert(
+            context,
+            check_tape,
+            VerificationError::InvalidFinalTape.cmr(),
+        )
+        .unwrap()
+    }
+}